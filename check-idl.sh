@@ -0,0 +1,38 @@
+#!/bin/bash
+# Builds each program's IDL the way `anchor build` does and fails if it
+# references a type it does not define. Anchor leaves out types it cannot
+# represent (tuples, for instance) without an error, which breaks clients.
+#
+# Usage: ./check-idl.sh [program...]   (defaults to every program)
+
+set -euo pipefail
+cd "$(dirname "$0")"
+
+PROGRAMS=("$@")
+if [ ${#PROGRAMS[@]} -eq 0 ]; then
+    PROGRAMS=(vault cliff_release_oracle stream_vault)
+fi
+
+STATUS=0
+for PROGRAM in "${PROGRAMS[@]}"; do
+    # stream_vault is its own workspace on an older anchor, whose markers have an extra dash.
+    IDL=$(cargo test --manifest-path "programs/$PROGRAM/Cargo.toml" --features idl-build --lib __anchor_private_print_idl_program -- --nocapture --quiet 2>/dev/null \
+        | sed -n '/^---* IDL begin program ---*$/,/^---* IDL end program ---*$/p' | sed '1d;$d')
+    if [ -z "$IDL" ]; then
+        echo "❌ $PROGRAM: could not build the IDL"
+        STATUS=1
+        continue
+    fi
+    MISSING=$(echo "$IDL" | jq -r '
+        ([(.types // [])[].name] + [(.accounts // [])[] | select(has("type")) | .name]) as $defined
+        | [.. | objects | select(has("defined")) | .defined | if type == "object" then .name else . end]
+        | unique - $defined | .[]')
+    if [ -n "$MISSING" ]; then
+        echo "❌ $PROGRAM: IDL references undefined types:"
+        echo "$MISSING" | sed 's/^/   /'
+        STATUS=1
+    else
+        echo "✅ $PROGRAM: every referenced type is defined"
+    fi
+done
+exit $STATUS
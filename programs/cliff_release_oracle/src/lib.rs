@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+declare_id!("FoYBmgQQHwKMRaJXcWqA2ZN7ubGydN6UKcdCVmV4FLGW");
+// Sample implementation of the vault's release-oracle interface (see
+// `vault::release_oracle`): a cliff that releases `cliff_bps` of the total at
+// `cliff_time`, then streams the rest linearly until `end_time`.
+#[program]
+pub mod cliff_release_oracle {
+    use super::*;
+    pub fn releasable_amount(_ctx: Context<ReleasableAmount>, query: ReleaseQuery) -> Result<u64> {
+        let schedule = CliffSchedule::try_from_slice(&query.data)
+            .map_err(|_| OracleError::InvalidScheduleData)?;
+        schedule.releasable(query.total_amount, query.schedule_time)
+    }
+}
+#[derive(Accounts)]
+pub struct ReleasableAmount<'info> {
+    /// CHECK: The escrow being queried; this schedule only needs the query
+    pub escrow_vault: UncheckedAccount<'info>,
+}
+// Mirror of `vault::release_oracle::ReleaseQuery`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReleaseQuery {
+    pub total_amount: u64,
+    pub released_amount: u64,
+    pub data: Vec<u8>,
+    pub schedule_time: i64,
+}
+// Layout of `ReleaseSchedule::Oracle::data` for this oracle.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct CliffSchedule {
+    pub cliff_time: i64,
+    pub end_time: i64,
+    pub cliff_bps: u16,
+}
+impl CliffSchedule {
+    pub fn releasable(&self, total_amount: u64, time: i64) -> Result<u64> {
+        require!(
+            self.cliff_time < self.end_time && self.cliff_bps <= 10000,
+            OracleError::InvalidScheduleData
+        );
+        if time < self.cliff_time {
+            return Ok(0);
+        }
+        if time >= self.end_time {
+            return Ok(total_amount);
+        }
+        let cliff_amount = (total_amount as u128) * (self.cliff_bps as u128) / 10000;
+        let streamed = (total_amount as u128 - cliff_amount)
+            * ((time - self.cliff_time) as u128)
+            / ((self.end_time - self.cliff_time) as u128);
+        Ok((cliff_amount + streamed) as u64)
+    }
+}
+#[error_code]
+pub enum OracleError {
+    #[msg("Oracle schedule data is not a valid cliff schedule")]
+    InvalidScheduleData,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_cliff_then_linear_release() {
+        let schedule = CliffSchedule { cliff_time: 100, end_time: 200, cliff_bps: 2_500 };
+        assert_eq!(schedule.releasable(1_000, 99).unwrap(), 0);
+        assert_eq!(schedule.releasable(1_000, 100).unwrap(), 250);
+        assert_eq!(schedule.releasable(1_000, 150).unwrap(), 625);
+        assert_eq!(schedule.releasable(1_000, 200).unwrap(), 1_000);
+        assert!(CliffSchedule { cliff_bps: 10_001, ..schedule }.releasable(1_000, 150).is_err());
+    }
+}
\ No newline at end of file
@@ -1,11 +1,20 @@
 use anchor_lang::prelude::*;
+use solana_program::pubkey;
+pub const STREAM_VERSION: u8 = 3;
 pub const MAX_PAUSE_RESUME_COUNT: u8 = 3;
+pub const MAX_STREAM_RECIPIENTS: usize = 5;
+pub const BASIS_POINTS_DENOMINATOR: u16 = 10_000;
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+pub const MAX_PROPOSAL_LIFETIME: i64 = 7 * 24 * 60 * 60;
+pub const MAX_FEE_EXEMPT_ACCOUNTS: usize = 10;
+pub const NOMINAL_SLOT_DURATION_MS: u32 = 400;
 pub const MIN_WITHDRAWAL_AMOUNT_USDC: u64 = 10_000_000;
 pub const MIN_WITHDRAWAL_AMOUNT_SOL: u64 = 10_000_000;
 pub const USDC_MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
 pub const USDT_MINT: Pubkey = pubkey!("Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB");
 pub const USDC_MINT_DEVNET: Pubkey = pubkey!("4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU");
 pub const WSOL_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+pub const VAULT_PROGRAM_ID: Pubkey = pubkey!("3X4cA5KNBo6yYw2aLwRgVPaX3RshUR62Y2Bo8GyXzYWg");
 pub fn is_supported_mint(mint: &Pubkey) -> bool {
     *mint == USDC_MINT || 
     *mint == USDT_MINT || 
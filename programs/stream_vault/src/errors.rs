@@ -67,4 +67,58 @@ pub enum StreamVaultError {
     InsufficientTokenBalance,
     #[msg("Invalid stream duration - must be one of the predefined durations")]
     InvalidStreamDuration,
+    #[msg("Stream must have between 1 and 5 recipients")]
+    InvalidRecipientCount,
+    #[msg("Recipient shares must be non-zero and sum to 10000 basis points")]
+    InvalidRecipientShares,
+    #[msg("Recipient listed more than once")]
+    DuplicateRecipient,
+    #[msg("Recipient token accounts do not match stream recipients")]
+    RecipientAccountMismatch,
+    #[msg("Invalid multisig configuration")]
+    InvalidMultisigConfig,
+    #[msg("Stream is governed by a multisig - submit a proposal instead")]
+    MultisigRequired,
+    #[msg("Stream multisig is already configured")]
+    MultisigAlreadyConfigured,
+    #[msg("Signer has already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Proposal has not reached the approval threshold")]
+    ThresholdNotMet,
+    #[msg("Invalid stream manager")]
+    InvalidManager,
+    #[msg("Invalid fee configuration")]
+    InvalidFeeConfig,
+    #[msg("Fee recipient token account missing or invalid")]
+    InvalidFeeAccount,
+    #[msg("Stream receivable is already tokenized")]
+    ReceivableAlreadyMinted,
+    #[msg("Only single-recipient streams can be tokenized")]
+    ReceivableRequiresSingleRecipient,
+    #[msg("Signer does not hold the stream receivable token")]
+    NotReceivableHolder,
+    #[msg("Stream renewal is not enabled")]
+    RenewalNotEnabled,
+    #[msg("Invalid renewal configuration")]
+    InvalidRenewalConfig,
+    #[msg("Escrow accounts are required for streams held in vault custody")]
+    MissingEscrowAccounts,
+    #[msg("Escrow account does not belong to this stream")]
+    InvalidEscrowAccount,
+    #[msg("Stream funds are already held in vault custody")]
+    StreamAlreadyInEscrow,
+    #[msg("Account layout version is not supported by this program")]
+    UnsupportedAccountVersion,
+    #[msg("Account is already on the current layout")]
+    AccountAlreadyMigrated,
+    #[msg("Stream start time is further in the past than the config allows")]
+    StartTimeInPast,
+    #[msg("Invalid start time policy")]
+    InvalidStartTimePolicy,
+    #[msg("Max slot duration must be 0 or at least the nominal slot time")]
+    InvalidSlotDuration,
 }
\ No newline at end of file
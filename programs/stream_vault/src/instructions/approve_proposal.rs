@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::{StreamMultisig, StreamProposal};
+use crate::errors::StreamVaultError;
+use crate::utils::get_current_time;
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    #[account(
+        constraint = multisig.is_signer(&approver.key()) @ StreamVaultError::Unauthorized,
+    )]
+    pub multisig: Account<'info, StreamMultisig>,
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.stream.as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = multisig,
+    )]
+    pub proposal: Account<'info, StreamProposal>,
+    pub approver: Signer<'info>,
+}
+pub fn handler(ctx: Context<ApproveProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let current_time = get_current_time()?;
+    require!(!proposal.executed, StreamVaultError::ProposalAlreadyExecuted);
+    require!(!proposal.is_expired(current_time), StreamVaultError::ProposalExpired);
+    let multisig = &ctx.accounts.multisig;
+    proposal.approvals.retain(|approver| multisig.is_signer(approver));
+    proposal.approve(ctx.accounts.approver.key())?;
+    msg!(
+        "Proposal {} approved by {} ({}/{})",
+        proposal.proposal_id,
+        ctx.accounts.approver.key(),
+        proposal.approvals.len(),
+        ctx.accounts.multisig.threshold
+    );
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::Stream;
+use crate::errors::StreamVaultError;
+use crate::vault_cpi::EscrowAccounts;
+use super::close_stream::settle_and_close;
+
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    #[account(
+        mut,
+        seeds = [b"stream", employer.key().as_ref(), stream.load()?.stream_id.to_le_bytes().as_ref()],
+        bump = stream.load()?.bump,
+        has_one = employer,
+        has_one = vault,
+        close = employer
+    )]
+    pub stream: AccountLoader<'info, Stream>,
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub employer_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = fee_token_account.owner == stream.load()?.fee_recipient @ StreamVaultError::InvalidFeeAccount,
+        constraint = fee_token_account.mint == stream.load()?.mint @ StreamVaultError::InvalidFeeAccount,
+    )]
+    pub fee_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub employer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Matched against `stream.escrow`; validated by the vault program
+    #[account(mut)]
+    pub escrow_vault: Option<UncheckedAccount<'info>>,
+    /// CHECK: Validated by the vault program
+    #[account(mut)]
+    pub escrow_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: Must be the vault program
+    pub vault_program: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CancelStream<'info>>) -> Result<()> {
+    let stream = *ctx.accounts.stream.load()?;
+    require!(stream.multisig().is_none(), StreamVaultError::MultisigRequired);
+    let current_time = stream.current_time()?;
+
+    let escrow = EscrowAccounts::load(
+        stream.escrow(),
+        ctx.accounts.escrow_vault.as_ref(),
+        ctx.accounts.escrow_token_account.as_ref(),
+        ctx.accounts.vault_program.as_ref(),
+    )?;
+    let refunded_amount = settle_and_close(
+        &ctx.accounts.stream,
+        &ctx.accounts.vault,
+        &ctx.accounts.employer_token_account,
+        ctx.accounts.fee_token_account.as_ref(),
+        &ctx.accounts.employer.to_account_info(),
+        &ctx.accounts.token_program,
+        ctx.remaining_accounts,
+        escrow,
+        current_time,
+    )?;
+
+    msg!(
+        "Stream cancelled: ID={}, employer={}, refunded_amount={}",
+        stream.stream_id,
+        ctx.accounts.employer.key(),
+        refunded_amount
+    );
+
+    Ok(())
+}
@@ -1,87 +1,250 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer, CloseAccount};
-use crate::state::Stream;
+use crate::state::{Stream, FeeMode};
 use crate::errors::StreamVaultError;
-use crate::utils::get_current_time;
+use crate::vault_cpi::EscrowAccounts;
 
 #[derive(Accounts)]
 pub struct CloseStream<'info> {
     #[account(
         mut,
-        seeds = [b"stream", employer.key().as_ref(), stream.stream_id.to_le_bytes().as_ref()],
-        bump = stream.bump,
+        seeds = [b"stream", employer.key().as_ref(), stream.load()?.stream_id.to_le_bytes().as_ref()],
+        bump = stream.load()?.bump,
         has_one = employer,
         has_one = vault,
         close = employer
     )]
-    pub stream: Account<'info, Stream>,
+    pub stream: AccountLoader<'info, Stream>,
     #[account(mut)]
     pub vault: Account<'info, TokenAccount>,
     #[account(mut)]
     pub employer_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = fee_token_account.owner == stream.load()?.fee_recipient @ StreamVaultError::InvalidFeeAccount,
+        constraint = fee_token_account.mint == stream.load()?.mint @ StreamVaultError::InvalidFeeAccount,
+    )]
+    pub fee_token_account: Option<Account<'info, TokenAccount>>,
     #[account(mut)]
     pub employer: Signer<'info>,
     pub token_program: Program<'info, Token>,
+    /// CHECK: Matched against `stream.escrow`; validated by the vault program
+    #[account(mut)]
+    pub escrow_vault: Option<UncheckedAccount<'info>>,
+    /// CHECK: Validated by the vault program
+    #[account(mut)]
+    pub escrow_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: Must be the vault program
+    pub vault_program: Option<UncheckedAccount<'info>>,
 }
 
-pub fn handler(ctx: Context<CloseStream>) -> Result<()> {
-    let stream = &ctx.accounts.stream;
-    let current_time = get_current_time()?;
-    
-    if !stream.is_ended(current_time) && stream.withdrawn_amount < stream.total_amount {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CloseStream<'info>>) -> Result<()> {
+    let stream = *ctx.accounts.stream.load()?;
+    require!(stream.multisig().is_none(), StreamVaultError::MultisigRequired);
+    let current_time = stream.current_time()?;
+
+    if !stream.is_ended(current_time) && stream.withdrawn_amount < stream.total_payable() {
         return Err(error!(StreamVaultError::StreamNotEnded));
     }
 
-    // If there are remaining funds in the vault, transfer them back to the employer
-    if ctx.accounts.vault.amount > 0 {
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.vault.to_account_info(),
-            to: ctx.accounts.employer_token_account.to_account_info(),
-            authority: ctx.accounts.stream.to_account_info(),
-        };
-        
-        let cpi_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-        );
-        
-        token::transfer(cpi_ctx, ctx.accounts.vault.amount)?;
-    }
-    
-    // Close the vault account
-    let close_accounts = CloseAccount {
-        account: ctx.accounts.vault.to_account_info(),
-        destination: ctx.accounts.employer.to_account_info(),
-        authority: ctx.accounts.stream.to_account_info(),
+    let escrow = EscrowAccounts::load(
+        stream.escrow(),
+        ctx.accounts.escrow_vault.as_ref(),
+        ctx.accounts.escrow_token_account.as_ref(),
+        ctx.accounts.vault_program.as_ref(),
+    )?;
+    let refunded_amount = settle_and_close(
+        &ctx.accounts.stream,
+        &ctx.accounts.vault,
+        &ctx.accounts.employer_token_account,
+        ctx.accounts.fee_token_account.as_ref(),
+        &ctx.accounts.employer.to_account_info(),
+        &ctx.accounts.token_program,
+        ctx.remaining_accounts,
+        escrow,
+        current_time,
+    )?;
+
+    msg!(
+        "Stream closed: ID={}, employer={}, refunded_amount={}",
+        stream.stream_id,
+        ctx.accounts.employer.key(),
+        refunded_amount
+    );
+
+    Ok(())
+}
+
+// Pays every recipient their outstanding share as of `settle_time` (less any
+// withdrawal fee), refunds the rest of the vault to the employer and closes the
+// vault. `recipient_token_accounts` must be passed in the same order as `stream.recipients`.
+// For streams in vault custody the owed amount is released from the escrow first and
+// the escrow is closed afterwards, refunding its balance to the employer.
+#[allow(clippy::too_many_arguments)]
+pub fn settle_and_close<'info>(
+    stream_account: &AccountLoader<'info, Stream>,
+    vault: &Account<'info, TokenAccount>,
+    employer_token_account: &Account<'info, TokenAccount>,
+    fee_token_account: Option<&Account<'info, TokenAccount>>,
+    employer: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    recipient_token_accounts: &'info [AccountInfo<'info>],
+    escrow: Option<EscrowAccounts<'info>>,
+    settle_time: i64,
+) -> Result<u64> {
+    let stream = *stream_account.load()?;
+    // A tokenized receivable pays its current holder, who proves ownership with
+    // their receivable token account passed after the recipient accounts.
+    let receivable_holder = match stream.receivable_mint() {
+        Some(_) => {
+            let receivable_info = recipient_token_accounts
+                .get(stream.recipients().len())
+                .ok_or(error!(StreamVaultError::RecipientAccountMismatch))?;
+            let receivable = Account::<TokenAccount>::try_from(receivable_info)?;
+            require!(
+                stream.is_receivable_holder(&receivable.owner, &receivable),
+                StreamVaultError::RecipientAccountMismatch
+            );
+            Some(receivable.owner)
+        },
+        None => None,
     };
-    
-    let employer_key = ctx.accounts.employer.key();
+    require!(
+        recipient_token_accounts.len() == stream.recipients().len() + receivable_holder.is_some() as usize,
+        StreamVaultError::RecipientAccountMismatch
+    );
+
+    let employer_key = stream.employer;
     let stream_id_bytes = stream.stream_id.to_le_bytes();
-    let bump = stream.bump;
-    let bump_array = [bump];
-    
+    let bump_array = [stream.bump];
     let seeds = &[
         b"stream".as_ref(),
         employer_key.as_ref(),
         &stream_id_bytes,
         &bump_array,
     ][..];
-    
     let signer_seeds = &[seeds];
+
+    let mut remaining = vault.amount;
+    if let Some(escrow) = escrow.as_ref() {
+        let mut owed_total = 0u64;
+        if stream.is_funded() {
+            for index in 0..stream.recipients().len() {
+                owed_total = owed_total
+                    .checked_add(stream.calculate_recipient_withdrawable_amount(index, settle_time)?)
+                    .ok_or(error!(StreamVaultError::MathOverflow))?;
+            }
+        }
+        let shortfall = owed_total.saturating_sub(remaining);
+        if shortfall > 0 {
+            escrow.release_to_stream(
+                stream_account.to_account_info(),
+                vault.to_account_info(),
+                token_program.to_account_info(),
+                shortfall,
+                signer_seeds,
+            )?;
+            remaining = token::accessor::amount(&vault.to_account_info())?;
+        }
+    }
+    for (index, account_info) in recipient_token_accounts[..stream.recipients().len()].iter().enumerate() {
+        let recipient_token_account = Account::<TokenAccount>::try_from(account_info)?;
+        let payee = match receivable_holder {
+            Some(holder) if index == 0 => holder,
+            _ => stream.recipients()[index].recipient,
+        };
+        require!(
+            recipient_token_account.owner == payee &&
+            recipient_token_account.mint == stream.mint,
+            StreamVaultError::RecipientAccountMismatch
+        );
+        let owed = if stream.is_funded() {
+            stream.calculate_recipient_withdrawable_amount(index, settle_time)?
+        } else {
+            0
+        };
+        let payout = owed.min(remaining);
+        if payout == 0 {
+            continue;
+        }
+        let fee_amount = stream.calculate_fee(payout, FeeMode::OnWithdrawal)?;
+        if fee_amount > 0 {
+            let fee_token_account = fee_token_account
+                .ok_or(error!(StreamVaultError::InvalidFeeAccount))?;
+            let cpi_accounts = Transfer {
+                from: vault.to_account_info(),
+                to: fee_token_account.to_account_info(),
+                authority: stream_account.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token::transfer(cpi_ctx, fee_amount)?;
+        }
+        let cpi_accounts = Transfer {
+            from: vault.to_account_info(),
+            to: account_info.clone(),
+            authority: stream_account.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, payout - fee_amount)?;
+        remaining = remaining
+            .checked_sub(payout)
+            .ok_or(error!(StreamVaultError::MathOverflow))?;
+        msg!(
+            "Settled {} tokens to recipient {}",
+            payout,
+            payee
+        );
+    }
+
+    // If there are remaining funds in the vault, transfer them back to the employer
+    if remaining > 0 {
+        let cpi_accounts = Transfer {
+            from: vault.to_account_info(),
+            to: employer_token_account.to_account_info(),
+            authority: stream_account.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        token::transfer(cpi_ctx, remaining)?;
+    }
+
+    // Close the vault account
+    let close_accounts = CloseAccount {
+        account: vault.to_account_info(),
+        destination: employer.clone(),
+        authority: stream_account.to_account_info(),
+    };
+
     let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
+        token_program.to_account_info(),
         close_accounts,
         signer_seeds,
     );
-    
+
     token::close_account(cpi_ctx)?;
-    
-    msg!(
-        "Stream closed: ID={}, employer={}, refunded_amount={}",
-        stream.stream_id,
-        ctx.accounts.employer.key(),
-        ctx.accounts.vault.amount
-    );
-    
-    Ok(())
+
+    if let Some(escrow) = escrow {
+        escrow.close(
+            stream_account.to_account_info(),
+            employer.clone(),
+            employer_token_account.to_account_info(),
+            token_program.to_account_info(),
+            signer_seeds,
+        )?;
+    }
+
+    Ok(remaining)
 }
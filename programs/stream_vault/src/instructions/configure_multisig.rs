@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::{Stream, StreamMultisig};
+use crate::errors::StreamVaultError;
+#[derive(Accounts)]
+pub struct ConfigureMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [b"stream", employer.key().as_ref(), stream.load()?.stream_id.to_le_bytes().as_ref()],
+        bump = stream.load()?.bump,
+        has_one = employer,
+    )]
+    pub stream: AccountLoader<'info, Stream>,
+    #[account(
+        init,
+        payer = employer,
+        space = StreamMultisig::LEN,
+        seeds = [b"multisig", stream.key().as_ref()],
+        bump
+    )]
+    pub multisig: Account<'info, StreamMultisig>,
+    #[account(mut)]
+    pub employer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+pub fn handler(ctx: Context<ConfigureMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    let stream_key = ctx.accounts.stream.key();
+    let mut stream = ctx.accounts.stream.load_mut()?;
+    require!(stream.multisig().is_none(), StreamVaultError::MultisigAlreadyConfigured);
+    StreamMultisig::validate(&signers, threshold)?;
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.stream = stream_key;
+    multisig.signers = signers;
+    multisig.threshold = threshold;
+    multisig.proposal_count = 0;
+    multisig.bump = ctx.bumps.multisig;
+    stream.set_multisig(Some(multisig.key()));
+    msg!(
+        "Stream {} now requires {} of {} employer approvals",
+        stream.stream_id,
+        threshold,
+        multisig.signers.len()
+    );
+    Ok(())
+}
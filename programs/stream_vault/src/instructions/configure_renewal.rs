@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::{Stream, StreamRenewal};
+use crate::errors::StreamVaultError;
+#[derive(Accounts)]
+pub struct ConfigureRenewal<'info> {
+    #[account(
+        mut,
+        seeds = [b"stream", employer.key().as_ref(), stream.load()?.stream_id.to_le_bytes().as_ref()],
+        bump = stream.load()?.bump,
+        has_one = employer,
+    )]
+    pub stream: AccountLoader<'info, Stream>,
+    pub employer: Signer<'info>,
+}
+pub fn handler(ctx: Context<ConfigureRenewal>, max_periods: u32) -> Result<()> {
+    let mut stream = ctx.accounts.stream.load_mut()?;
+    require!(stream.multisig().is_none(), StreamVaultError::MultisigRequired);
+    let periods_completed = stream.renewal().map(|r| r.periods_completed).unwrap_or(0);
+    require!(max_periods > periods_completed, StreamVaultError::InvalidRenewalConfig);
+    stream.set_renewal(Some(StreamRenewal {
+        periods_completed,
+        ..StreamRenewal::new(max_periods)
+    }));
+    msg!(
+        "Stream {} renews for up to {} periods",
+        stream.stream_id,
+        max_periods
+    );
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::state::{Stream, StreamMultisig, StreamProposal, StreamAction};
+use crate::errors::StreamVaultError;
+use crate::utils::get_current_time;
+use crate::constants::MAX_PROPOSAL_LIFETIME;
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(
+        seeds = [b"stream", stream.load()?.employer.as_ref(), stream.load()?.stream_id.to_le_bytes().as_ref()],
+        bump = stream.load()?.bump,
+        constraint = stream.load()?.multisig() == Some(multisig.key()) @ StreamVaultError::Unauthorized,
+    )]
+    pub stream: AccountLoader<'info, Stream>,
+    #[account(
+        mut,
+        seeds = [b"multisig", stream.key().as_ref()],
+        bump = multisig.bump,
+        constraint = multisig.is_signer(&proposer.key()) @ StreamVaultError::Unauthorized,
+    )]
+    pub multisig: Account<'info, StreamMultisig>,
+    #[account(
+        init,
+        payer = proposer,
+        space = StreamProposal::LEN,
+        seeds = [b"proposal", stream.key().as_ref(), multisig.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, StreamProposal>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+pub fn handler(ctx: Context<CreateProposal>, action: StreamAction, lifetime: i64) -> Result<()> {
+    require!(
+        lifetime > 0 && lifetime <= MAX_PROPOSAL_LIFETIME,
+        StreamVaultError::InvalidTimeRange
+    );
+    let current_time = get_current_time()?;
+    let multisig = &mut ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.stream = ctx.accounts.stream.key();
+    proposal.multisig = multisig.key();
+    proposal.proposal_id = multisig.proposal_count;
+    proposal.action = action;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.approvals = vec![ctx.accounts.proposer.key()];
+    proposal.created_at = current_time;
+    proposal.expires_at = current_time
+        .checked_add(lifetime)
+        .ok_or(error!(StreamVaultError::MathOverflow))?;
+    proposal.executed = false;
+    proposal.bump = ctx.bumps.proposal;
+    multisig.proposal_count = multisig.proposal_count
+        .checked_add(1)
+        .ok_or(error!(StreamVaultError::MathOverflow))?;
+    msg!(
+        "Proposal {} created for stream {}: action={:?}, expires_at={}",
+        proposal.proposal_id,
+        ctx.accounts.stream.load()?.stream_id,
+        action,
+        proposal.expires_at
+    );
+    Ok(())
+}
@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use anchor_spl::associated_token::AssociatedToken;
-use crate::state::{Stream, StreamDuration};
+use crate::state::{Stream, StreamConfig, StreamDuration, RecipientShare, build_recipients};
 use crate::strategies::AccelerationType;
 use crate::utils::{validate_time_range, validate_amount, get_current_time, validate_mint_for_network, validate_mint_decimals};
-use crate::constants::is_supported_mint;
+use crate::constants::{is_supported_mint, VAULT_PROGRAM_ID, STREAM_VERSION};
 use crate::errors::StreamVaultError;
+use crate::vault_cpi::{self, ReleaseAuthority, escrow_vault_id, release_schedule_for};
 #[derive(Accounts)]
 #[instruction(stream_id: u64)]
 pub struct CreateStream<'info> {
@@ -16,7 +17,7 @@ pub struct CreateStream<'info> {
         seeds = [b"stream", employer.key().as_ref(), stream_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub stream: Account<'info, Stream>,
+    pub stream: AccountLoader<'info, Stream>,
     #[account(
         init,
         payer = employer,
@@ -24,6 +25,11 @@ pub struct CreateStream<'info> {
         associated_token::authority = stream,
     )]
     pub vault: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"stream_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StreamConfig>,
     #[account(mut)]
     pub employer: Signer<'info>,
     /// CHECK: The freelancer account is validated in the handler to ensure it's a valid Solana address
@@ -32,6 +38,20 @@ pub struct CreateStream<'info> {
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+    /// CHECK: Initialized and validated by the vault program
+    #[account(mut)]
+    pub escrow_vault: Option<UncheckedAccount<'info>>,
+    /// CHECK: Initialized and validated by the vault program
+    #[account(mut)]
+    pub escrow_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: Validated by the vault program
+    pub escrow_config: Option<UncheckedAccount<'info>>,
+    /// CHECK: Must be the vault program
+    #[account(address = VAULT_PROGRAM_ID)]
+    pub vault_program: Option<UncheckedAccount<'info>>,
+    /// CHECK: This program, recorded as the escrow's owner program
+    #[account(address = crate::ID)]
+    pub stream_program: Option<UncheckedAccount<'info>>,
 }
 pub fn handler(
     ctx: Context<CreateStream>,
@@ -40,12 +60,18 @@ pub fn handler(
     stream_duration: StreamDuration,
     total_amount: u64,
     acceleration_type: Option<AccelerationType>,
+    recipients: Option<Vec<RecipientShare>>,
 ) -> Result<()> {
     let current_time = get_current_time()?;
     let duration_seconds = stream_duration.to_seconds();
     let end_time = start_time.checked_add(duration_seconds)
         .ok_or(error!(StreamVaultError::MathOverflow))?;
-    validate_time_range(start_time, end_time, current_time)?;
+    validate_time_range(start_time, end_time)?;
+    let start_time_policy = match ctx.accounts.vault_program {
+        Some(_) => ctx.accounts.config.start_time_policy.for_escrow(),
+        None => ctx.accounts.config.start_time_policy,
+    };
+    start_time_policy.validate_start(start_time, current_time)?;
     validate_amount(total_amount)?;
     msg!("Creating stream with duration: {} ({} days)", stream_duration.to_days(), duration_seconds / 86400);
     let mint_key = ctx.accounts.mint.key();
@@ -72,31 +98,89 @@ pub fn handler(
         end_time < i64::MAX / 2, 
         StreamVaultError::InvalidTimestamp
     );
-    let stream = &mut ctx.accounts.stream;
-    stream.employer = ctx.accounts.employer.key();
-    stream.freelancer = ctx.accounts.freelancer.key();
-    stream.stream_id = stream_id;
-    stream.start_time = start_time;
-    stream.end_time = end_time;
-    stream.total_amount = total_amount;
-    stream.withdrawn_amount = 0;
-    stream.is_paused = false;
-    stream.pause_time = None;
-    stream.total_paused_duration = 0;
-    stream.pause_resume_count = 0;
-    stream.last_operation_slot = 0; 
-    stream.accumulated_dust = 0; 
-    stream.mint = ctx.accounts.mint.key();
-    stream.vault = ctx.accounts.vault.key();
-    stream.acceleration_type = acceleration_type.unwrap_or(AccelerationType::Linear);
-    stream.bump = ctx.bumps.stream;
+    let recipients = build_recipients(ctx.accounts.freelancer.key(), recipients)?;
+    let acceleration_type = acceleration_type.unwrap_or(AccelerationType::Linear);
+    let config = &ctx.accounts.config;
+    let fee_exempt = config.is_exempt(&ctx.accounts.employer.key()) || config.is_exempt(&ctx.accounts.freelancer.key());
+    let fee_basis_points = if fee_exempt { 0 } else { config.fee_basis_points };
+    {
+        let stream = &mut ctx.accounts.stream.load_init()?;
+        stream.version = STREAM_VERSION;
+        stream.employer = ctx.accounts.employer.key();
+        stream.freelancer = ctx.accounts.freelancer.key();
+        stream.stream_id = stream_id;
+        stream.start_time = start_time;
+        stream.end_time = end_time;
+        stream.total_amount = total_amount;
+        stream.withdrawn_amount = 0;
+        stream.is_paused = 0;
+        stream.pause_time = 0;
+        stream.total_paused_duration = 0;
+        stream.pause_resume_count = 0;
+        stream.last_operation_slot = 0; 
+        stream.accumulated_dust = 0; 
+        stream.mint = ctx.accounts.mint.key();
+        stream.vault = ctx.accounts.vault.key();
+        stream.set_acceleration_type(acceleration_type);
+        stream.bump = ctx.bumps.stream;
+        stream.set_recipients(&recipients)?;
+        stream.set_multisig(None);
+        stream.set_manager(None);
+        stream.fee_basis_points = fee_basis_points;
+        stream.set_fee_mode(config.fee_mode);
+        stream.fee_recipient = config.fee_recipient;
+        stream.fees_paid = 0;
+        stream.set_receivable_mint(None);
+        stream.set_renewal(None);
+        stream.carried_over_amount = 0;
+        stream.set_escrow(None);
+        stream.set_start_time_policy(start_time_policy);
+        stream.max_slot_duration_ms = config.max_slot_duration_ms;
+    }
+    // Streams created with the escrow accounts hand custody to the vault program,
+    // which releases funds on the same schedule to the stream PDA only.
+    if let Some(vault_program) = ctx.accounts.vault_program.as_ref() {
+        let (Some(escrow_vault), Some(escrow_token_account), Some(escrow_config), Some(stream_program)) = (
+            ctx.accounts.escrow_vault.as_ref(),
+            ctx.accounts.escrow_token_account.as_ref(),
+            ctx.accounts.escrow_config.as_ref(),
+            ctx.accounts.stream_program.as_ref(),
+        ) else {
+            return Err(error!(StreamVaultError::MissingEscrowAccounts));
+        };
+        let stream_key = ctx.accounts.stream.key();
+        vault_cpi::create_escrow(
+            &vault_program.to_account_info(),
+            vault_cpi::CreateEscrow {
+                escrow_vault: escrow_vault.to_account_info(),
+                vault_token_account: escrow_token_account.to_account_info(),
+                config: escrow_config.to_account_info(),
+                depositor: ctx.accounts.employer.to_account_info(),
+                beneficiary: ctx.accounts.freelancer.to_account_info(),
+                owner_program: stream_program.to_account_info(),
+                owner_account: ctx.accounts.stream.to_account_info(),
+                token_mint: ctx.accounts.mint.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            escrow_vault_id(&stream_key),
+            total_amount,
+            release_schedule_for(acceleration_type, start_time, end_time, total_amount),
+            ReleaseAuthority::Program(stream_key),
+        )?;
+        ctx.accounts.stream.load_mut()?.set_escrow(Some(escrow_vault.key()));
+    }
     msg!(
-        "Stream created: ID={}, employer={}, freelancer={}, amount={}, acceleration={:?}",
+        "Stream created: ID={}, employer={}, freelancer={}, amount={}, acceleration={:?}, recipients={}, fee={} bps {:?}",
         stream_id,
         ctx.accounts.employer.key(),
         ctx.accounts.freelancer.key(),
         total_amount,
-        stream.acceleration_type
+        acceleration_type,
+        recipients.len(),
+        fee_basis_points,
+        config.fee_mode
     );
     Ok(())
 }
\ No newline at end of file
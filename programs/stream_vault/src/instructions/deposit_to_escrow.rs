@@ -1,39 +1,67 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::Stream;
+use crate::state::{Stream, FeeMode};
 use crate::errors::StreamVaultError;
-use crate::utils::{get_current_time, validate_token_account_not_frozen};
+use crate::utils::validate_token_account_not_frozen;
+use crate::vault_cpi::{self, EscrowAccounts};
 #[derive(Accounts)]
 pub struct DepositToEscrow<'info> {
     #[account(
         mut,
-        seeds = [b"stream", employer.key().as_ref(), stream.stream_id.to_le_bytes().as_ref()],
-        bump = stream.bump,
+        seeds = [b"stream", employer.key().as_ref(), stream.load()?.stream_id.to_le_bytes().as_ref()],
+        bump = stream.load()?.bump,
         has_one = employer,
         has_one = vault,
     )]
-    pub stream: Account<'info, Stream>,
+    pub stream: AccountLoader<'info, Stream>,
     #[account(mut)]
     pub vault: Account<'info, TokenAccount>,
     #[account(mut)]
     pub employer_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = fee_token_account.owner == stream.load()?.fee_recipient @ StreamVaultError::InvalidFeeAccount,
+        constraint = fee_token_account.mint == stream.load()?.mint @ StreamVaultError::InvalidFeeAccount,
+    )]
+    pub fee_token_account: Option<Account<'info, TokenAccount>>,
     #[account(mut)]
     pub employer: Signer<'info>,
     pub token_program: Program<'info, Token>,
+    /// CHECK: Matched against `stream.escrow`; validated by the vault program
+    #[account(mut)]
+    pub escrow_vault: Option<UncheckedAccount<'info>>,
+    /// CHECK: Validated by the vault program
+    #[account(mut)]
+    pub escrow_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: Validated by the vault program
+    pub escrow_config: Option<UncheckedAccount<'info>>,
+    /// CHECK: Must be the vault program
+    pub vault_program: Option<UncheckedAccount<'info>>,
+    /// CHECK: The vault program's fee token account; validated by the vault program
+    #[account(mut)]
+    pub escrow_fee_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: The vault program's fee ledger for the mint; validated by the vault program
+    #[account(mut)]
+    pub escrow_fee_ledger: Option<UncheckedAccount<'info>>,
 }
 pub fn handler(ctx: Context<DepositToEscrow>, amount: u64) -> Result<()> {
-    let stream = &mut ctx.accounts.stream;
-    let current_time = get_current_time()?;
+    let stream = *ctx.accounts.stream.load()?;
+    let current_time = stream.current_time()?;
     validate_token_account_not_frozen(&ctx.accounts.employer_token_account)?;
     validate_token_account_not_frozen(&ctx.accounts.vault)?;
+    let fee_amount = stream.calculate_fee(stream.total_amount, FeeMode::OnCreation)?;
+    let required_balance = stream.total_amount
+        .checked_add(fee_amount)
+        .ok_or(error!(StreamVaultError::MathOverflow))?;
     require!(
-        ctx.accounts.employer_token_account.amount >= stream.total_amount,
+        ctx.accounts.employer_token_account.amount >= required_balance,
         StreamVaultError::InsufficientFunds
     );
-    if stream.is_funded {
+    if stream.is_funded() {
         return Err(error!(StreamVaultError::StreamAlreadyFunded));
     }
-    if stream.start_time <= current_time && !stream.is_paused {
+    let started = stream.start_time <= current_time && !stream.is_paused();
+    if started && !stream.allows_late_funding() {
         return Err(error!(StreamVaultError::StreamAlreadyStarted));
     }
     if amount != stream.total_amount {
@@ -44,15 +72,72 @@ pub fn handler(ctx: Context<DepositToEscrow>, amount: u64) -> Result<()> {
         );
         return Err(error!(StreamVaultError::InvalidAmount));
     }
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.employer_token_account.to_account_info(),
-        to: ctx.accounts.vault.to_account_info(),
-        authority: ctx.accounts.employer.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    token::transfer(cpi_ctx, amount)?;
-    stream.is_funded = true;
+    let escrow = EscrowAccounts::load(
+        stream.escrow(),
+        ctx.accounts.escrow_vault.as_ref(),
+        ctx.accounts.escrow_token_account.as_ref(),
+        ctx.accounts.vault_program.as_ref(),
+    )?;
+    match escrow {
+        Some(escrow) => {
+            let escrow_config = ctx.accounts.escrow_config
+                .as_ref()
+                .ok_or(error!(StreamVaultError::MissingEscrowAccounts))?;
+            vault_cpi::deposit_funds(
+                &escrow.vault_program,
+                vault_cpi::DepositFunds {
+                    escrow_vault: escrow.escrow_vault.clone(),
+                    vault_token_account: escrow.escrow_token_account.clone(),
+                    depositor: ctx.accounts.employer.to_account_info(),
+                    depositor_token_account: ctx.accounts.employer_token_account.to_account_info(),
+                    config: escrow_config.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    fee_token_account: ctx.accounts.escrow_fee_token_account.as_ref().map(|account| account.to_account_info()),
+                    fee_ledger: ctx.accounts.escrow_fee_ledger.as_ref().map(|account| account.to_account_info()),
+                },
+                amount,
+            )?;
+        },
+        None => {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.employer_token_account.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.employer.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::transfer(cpi_ctx, amount)?;
+        },
+    }
+    let mut fees_paid = stream.fees_paid;
+    if fee_amount > 0 {
+        let fee_token_account = ctx.accounts.fee_token_account
+            .as_ref()
+            .ok_or(error!(StreamVaultError::InvalidFeeAccount))?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.employer_token_account.to_account_info(),
+            to: fee_token_account.to_account_info(),
+            authority: ctx.accounts.employer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, fee_amount)?;
+        fees_paid = fees_paid
+            .checked_add(fee_amount)
+            .ok_or(error!(StreamVaultError::MathOverflow))?;
+        msg!("Protocol fee of {} tokens collected", fee_amount);
+    }
+    let mut stream = ctx.accounts.stream.load_mut()?;
+    stream.fees_paid = fees_paid;
+    stream.set_funded(true);
+    // Without catch-up, a stream funded after its start accrues from the deposit onwards.
+    if started && !stream.catches_up() {
+        let duration = stream.get_stream_duration()?;
+        stream.start_time = current_time;
+        stream.end_time = current_time
+            .checked_add(duration)
+            .ok_or(error!(StreamVaultError::MathOverflow))?;
+        msg!("Stream {} funded late, schedule now starts at {}", stream.stream_id, current_time);
+    }
     msg!(
         "Stream {} fully funded with {} tokens",
         stream.stream_id,
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::{Stream, StreamMultisig, StreamProposal, StreamAction};
+use crate::errors::StreamVaultError;
+use crate::utils::get_current_time;
+use crate::vault_cpi::EscrowAccounts;
+use super::pause_stream::apply_pause;
+use super::resume_stream::apply_resume;
+use super::close_stream::settle_and_close;
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"stream", employer.key().as_ref(), stream.load()?.stream_id.to_le_bytes().as_ref()],
+        bump = stream.load()?.bump,
+        has_one = employer,
+        constraint = stream.load()?.multisig() == Some(multisig.key()) @ StreamVaultError::Unauthorized,
+    )]
+    pub stream: AccountLoader<'info, Stream>,
+    #[account(
+        mut,
+        constraint = multisig.is_signer(&executor.key()) @ StreamVaultError::Unauthorized,
+    )]
+    pub multisig: Account<'info, StreamMultisig>,
+    #[account(
+        mut,
+        seeds = [b"proposal", stream.key().as_ref(), proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = stream,
+        has_one = multisig,
+        has_one = proposer,
+        close = proposer,
+    )]
+    pub proposal: Account<'info, StreamProposal>,
+    pub executor: Signer<'info>,
+    /// CHECK: Validated against `proposal.proposer`; receives the proposal's rent once it executes
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    /// CHECK: Validated against `stream.employer`; receives refunds and rent when the stream is closed
+    #[account(mut)]
+    pub employer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = vault.key() == stream.load()?.vault @ StreamVaultError::Unauthorized,
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = employer_token_account.owner == stream.load()?.employer @ StreamVaultError::Unauthorized,
+        constraint = employer_token_account.mint == stream.load()?.mint @ StreamVaultError::InvalidMint,
+    )]
+    pub employer_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = fee_token_account.owner == stream.load()?.fee_recipient @ StreamVaultError::InvalidFeeAccount,
+        constraint = fee_token_account.mint == stream.load()?.mint @ StreamVaultError::InvalidFeeAccount,
+    )]
+    pub fee_token_account: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+    /// CHECK: Matched against `stream.escrow`; validated by the vault program
+    #[account(mut)]
+    pub escrow_vault: Option<UncheckedAccount<'info>>,
+    /// CHECK: Validated by the vault program
+    #[account(mut)]
+    pub escrow_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: Must be the vault program
+    pub vault_program: Option<UncheckedAccount<'info>>,
+}
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>) -> Result<()> {
+    let current_time = get_current_time()?;
+    let proposal = &ctx.accounts.proposal;
+    let multisig = &ctx.accounts.multisig;
+    require!(!proposal.executed, StreamVaultError::ProposalAlreadyExecuted);
+    require!(!proposal.is_expired(current_time), StreamVaultError::ProposalExpired);
+    let stream_time = ctx.accounts.stream.load()?.current_time()?;
+    let approvals = proposal.approvals
+        .iter()
+        .filter(|approver| multisig.is_signer(approver))
+        .count();
+    require!(
+        approvals >= multisig.threshold as usize,
+        StreamVaultError::ThresholdNotMet
+    );
+    let action = proposal.action;
+    match action {
+        StreamAction::Pause => {
+            let current_slot = Clock::get()?.slot;
+            apply_pause(&mut *ctx.accounts.stream.load_mut()?, stream_time, current_slot)?;
+        },
+        StreamAction::Resume => {
+            let current_slot = Clock::get()?.slot;
+            apply_resume(&mut *ctx.accounts.stream.load_mut()?, stream_time, current_slot)?;
+        },
+        StreamAction::Close | StreamAction::Cancel => {
+            let stream = *ctx.accounts.stream.load()?;
+            if action == StreamAction::Close {
+                require!(
+                    stream.is_ended(stream_time) || stream.withdrawn_amount >= stream.total_payable(),
+                    StreamVaultError::StreamNotEnded
+                );
+            }
+            let (Some(vault), Some(employer_token_account), Some(token_program)) = (
+                ctx.accounts.vault.as_ref(),
+                ctx.accounts.employer_token_account.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            ) else {
+                return Err(error!(StreamVaultError::RecipientAccountMismatch));
+            };
+            let escrow = EscrowAccounts::load(
+                stream.escrow(),
+                ctx.accounts.escrow_vault.as_ref(),
+                ctx.accounts.escrow_token_account.as_ref(),
+                ctx.accounts.vault_program.as_ref(),
+            )?;
+            let refunded_amount = settle_and_close(
+                &ctx.accounts.stream,
+                vault,
+                employer_token_account,
+                ctx.accounts.fee_token_account.as_ref(),
+                &ctx.accounts.employer.to_account_info(),
+                token_program,
+                ctx.remaining_accounts,
+                escrow,
+                stream_time,
+            )?;
+            ctx.accounts.stream.close(ctx.accounts.employer.to_account_info())?;
+            msg!("Stream {} refunded {} tokens to employer", stream.stream_id, refunded_amount);
+        },
+        StreamAction::SetManager { manager } => {
+            ctx.accounts.stream.load_mut()?.update_manager(manager)?;
+        },
+        StreamAction::AddSigner { .. } |
+        StreamAction::RemoveSigner { .. } |
+        StreamAction::ChangeThreshold { .. } => {
+            ctx.accounts.multisig.apply(action)?;
+        },
+    }
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.executed = true;
+    msg!(
+        "Proposal {} executed: action={:?}, executor={}",
+        proposal.proposal_id,
+        action,
+        ctx.accounts.executor.key()
+    );
+    Ok(())
+}
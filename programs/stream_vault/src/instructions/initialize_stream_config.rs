@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use crate::state::{StreamConfig, FeeMode, StartTimePolicy};
+use crate::errors::StreamVaultError;
+use crate::constants::{BASIS_POINTS_DENOMINATOR, MAX_FEE_EXEMPT_ACCOUNTS, NOMINAL_SLOT_DURATION_MS};
+#[derive(Accounts)]
+pub struct InitializeStreamConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = StreamConfig::LEN,
+        seeds = [b"stream_config"],
+        bump
+    )]
+    pub config: Account<'info, StreamConfig>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: The upgradeable loader's program data account for this program, read by upgrade_authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        owner = bpf_loader_upgradeable::ID,
+        constraint = upgrade_authority(&program_data)? == Some(authority.key()) @ StreamVaultError::Unauthorized,
+    )]
+    pub program_data: UncheckedAccount<'info>,
+    /// CHECK: The fee recipient only owns the token accounts that receive protocol fees
+    pub fee_recipient: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+pub fn handler(
+    ctx: Context<InitializeStreamConfig>,
+    fee_basis_points: u16,
+    fee_mode: FeeMode,
+    fee_exempt: Vec<Pubkey>,
+    start_time_policy: StartTimePolicy,
+    max_slot_duration_ms: u32,
+) -> Result<()> {
+    validate_fee_config(fee_basis_points, &fee_exempt)?;
+    validate_slot_duration(max_slot_duration_ms)?;
+    start_time_policy.validate()?;
+    let config = &mut ctx.accounts.config;
+    config.authority = ctx.accounts.authority.key();
+    config.fee_basis_points = fee_basis_points;
+    config.fee_recipient = ctx.accounts.fee_recipient.key();
+    config.fee_mode = fee_mode;
+    config.fee_exempt = fee_exempt;
+    config.start_time_policy = start_time_policy;
+    config.max_slot_duration_ms = max_slot_duration_ms;
+    config.bump = ctx.bumps.config;
+    msg!(
+        "Stream config initialized: fee={} bps, mode={:?}, exempt={}",
+        fee_basis_points,
+        fee_mode,
+        config.fee_exempt.len()
+    );
+    Ok(())
+}
+fn upgrade_authority(program_data: &AccountInfo) -> Result<Option<Pubkey>> {
+    let program_data = ProgramData::try_deserialize(&mut &program_data.try_borrow_data()?[..])?;
+    Ok(program_data.upgrade_authority_address)
+}
+pub fn validate_fee_config(fee_basis_points: u16, fee_exempt: &[Pubkey]) -> Result<()> {
+    require!(
+        fee_basis_points <= BASIS_POINTS_DENOMINATOR,
+        StreamVaultError::InvalidFeeConfig
+    );
+    require!(
+        fee_exempt.len() <= MAX_FEE_EXEMPT_ACCOUNTS,
+        StreamVaultError::InvalidFeeConfig
+    );
+    Ok(())
+}
+
+// Zero disables the clock guard; anything shorter than a slot would cap
+// accrual below real time on a healthy cluster.
+pub fn validate_slot_duration(max_slot_duration_ms: u32) -> Result<()> {
+    require!(
+        max_slot_duration_ms == 0 || max_slot_duration_ms >= NOMINAL_SLOT_DURATION_MS,
+        StreamVaultError::InvalidSlotDuration
+    );
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::system_program::{self, Transfer};
+use crate::state::Stream;
+use crate::errors::StreamVaultError;
+use crate::constants::STREAM_VERSION;
+#[derive(Accounts)]
+pub struct MigrateStream<'info> {
+    /// CHECK: Decoded by layout version in the handler; must be owned by this program
+    #[account(mut, owner = crate::ID)]
+    pub stream: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+// Upgrades a stream stored in an older layout in place. Anyone may run it; the
+// payer covers the rent for any extra space.
+pub fn handler(ctx: Context<MigrateStream>) -> Result<()> {
+    let stream_info = ctx.accounts.stream.to_account_info();
+    let (stream, from_version) = Stream::from_versioned_bytes(&stream_info.try_borrow_data()?)?;
+    require!(from_version < STREAM_VERSION, StreamVaultError::AccountAlreadyMigrated);
+    if stream_info.data_len() < Stream::LEN {
+        let required_lamports = Rent::get()?.minimum_balance(Stream::LEN);
+        let shortfall = required_lamports.saturating_sub(stream_info.lamports());
+        if shortfall > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: stream_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_ctx, shortfall)?;
+        }
+        stream_info.realloc(Stream::LEN, false)?;
+    }
+    let mut data = stream_info.try_borrow_mut_data()?;
+    data[..8].copy_from_slice(&Stream::DISCRIMINATOR);
+    data[8..Stream::LEN].copy_from_slice(bytemuck::bytes_of(&stream));
+    msg!(
+        "Stream {} migrated from v{} to v{}",
+        stream.stream_id,
+        from_version,
+        STREAM_VERSION
+    );
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::Stream;
+use crate::errors::StreamVaultError;
+use crate::constants::VAULT_PROGRAM_ID;
+use crate::vault_cpi::{self, ReleaseAuthority, ReleaseSchedule, escrow_vault_id, release_schedule_for};
+#[derive(Accounts)]
+pub struct MigrateStreamCustody<'info> {
+    #[account(
+        mut,
+        seeds = [b"stream", employer.key().as_ref(), stream.load()?.stream_id.to_le_bytes().as_ref()],
+        bump = stream.load()?.bump,
+        has_one = employer,
+        has_one = vault,
+        has_one = freelancer,
+        has_one = mint,
+    )]
+    pub stream: AccountLoader<'info, Stream>,
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = employer,
+    )]
+    pub employer_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub employer: Signer<'info>,
+    /// CHECK: Matched against `stream.freelancer`; recorded as the escrow beneficiary
+    pub freelancer: UncheckedAccount<'info>,
+    pub mint: Account<'info, Mint>,
+    /// CHECK: Initialized and validated by the vault program
+    #[account(mut)]
+    pub escrow_vault: UncheckedAccount<'info>,
+    /// CHECK: Initialized and validated by the vault program
+    #[account(mut)]
+    pub escrow_token_account: UncheckedAccount<'info>,
+    /// CHECK: Validated by the vault program
+    pub escrow_config: UncheckedAccount<'info>,
+    /// CHECK: Must be the vault program
+    #[account(address = VAULT_PROGRAM_ID)]
+    pub vault_program: UncheckedAccount<'info>,
+    /// CHECK: This program, recorded as the escrow's owner program
+    #[account(address = crate::ID)]
+    pub stream_program: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: The vault program's fee token account; validated by the vault program
+    #[account(mut)]
+    pub escrow_fee_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: The vault program's fee ledger for the mint; validated by the vault program
+    #[account(mut)]
+    pub escrow_fee_ledger: Option<UncheckedAccount<'info>>,
+}
+// Moves a stream created before vault custody into an escrow. Funded streams
+// escrow their current vault balance under an immediate schedule, since the
+// stream keeps its own earnings accounting and is the only release authority.
+// Unfunded streams get the same time-based escrow `create_stream` would open.
+pub fn handler(ctx: Context<MigrateStreamCustody>) -> Result<()> {
+    let stream = *ctx.accounts.stream.load()?;
+    require!(stream.escrow().is_none(), StreamVaultError::StreamAlreadyInEscrow);
+    let stream_key = ctx.accounts.stream.key();
+    let (escrow_amount, release_schedule) = if stream.is_funded() {
+        let balance = ctx.accounts.vault.amount;
+        require!(balance > 0, StreamVaultError::NoFundsAvailable);
+        (balance, ReleaseSchedule::Immediate)
+    } else {
+        (
+            stream.total_amount,
+            release_schedule_for(stream.acceleration_type(), stream.start_time, stream.end_time, stream.total_amount),
+        )
+    };
+    let employer_key = stream.employer;
+    let stream_id_bytes = stream.stream_id.to_le_bytes();
+    let bump_array = [stream.bump];
+    let seeds = &[
+        b"stream".as_ref(),
+        employer_key.as_ref(),
+        &stream_id_bytes,
+        &bump_array,
+    ][..];
+    let signer_seeds = &[seeds];
+
+    // The vault program only accepts deposits from the escrow depositor, so the
+    // balance is routed back through the employer's token account.
+    if stream.is_funded() {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.employer_token_account.to_account_info(),
+            authority: ctx.accounts.stream.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, escrow_amount)?;
+    }
+    vault_cpi::create_escrow(
+        &ctx.accounts.vault_program.to_account_info(),
+        vault_cpi::CreateEscrow {
+            escrow_vault: ctx.accounts.escrow_vault.to_account_info(),
+            vault_token_account: ctx.accounts.escrow_token_account.to_account_info(),
+            config: ctx.accounts.escrow_config.to_account_info(),
+            depositor: ctx.accounts.employer.to_account_info(),
+            beneficiary: ctx.accounts.freelancer.to_account_info(),
+            owner_program: ctx.accounts.stream_program.to_account_info(),
+            owner_account: ctx.accounts.stream.to_account_info(),
+            token_mint: ctx.accounts.mint.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+        escrow_vault_id(&stream_key),
+        escrow_amount,
+        release_schedule,
+        ReleaseAuthority::Program(stream_key),
+    )?;
+    if stream.is_funded() {
+        vault_cpi::deposit_funds(
+            &ctx.accounts.vault_program.to_account_info(),
+            vault_cpi::DepositFunds {
+                escrow_vault: ctx.accounts.escrow_vault.to_account_info(),
+                vault_token_account: ctx.accounts.escrow_token_account.to_account_info(),
+                depositor: ctx.accounts.employer.to_account_info(),
+                depositor_token_account: ctx.accounts.employer_token_account.to_account_info(),
+                config: ctx.accounts.escrow_config.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                fee_token_account: ctx.accounts.escrow_fee_token_account.as_ref().map(|account| account.to_account_info()),
+                fee_ledger: ctx.accounts.escrow_fee_ledger.as_ref().map(|account| account.to_account_info()),
+            },
+            escrow_amount,
+        )?;
+    }
+    ctx.accounts.stream.load_mut()?.set_escrow(Some(ctx.accounts.escrow_vault.key()));
+    msg!(
+        "Stream {} migrated to vault custody: escrow={}, amount={}",
+        stream.stream_id,
+        ctx.accounts.escrow_vault.key(),
+        escrow_amount
+    );
+    Ok(())
+}
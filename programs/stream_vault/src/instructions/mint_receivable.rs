@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use crate::state::Stream;
+use crate::errors::StreamVaultError;
+#[derive(Accounts)]
+pub struct MintReceivable<'info> {
+    #[account(
+        mut,
+        seeds = [b"stream", employer.key().as_ref(), stream.load()?.stream_id.to_le_bytes().as_ref()],
+        bump = stream.load()?.bump,
+        has_one = employer,
+        has_one = freelancer,
+    )]
+    pub stream: AccountLoader<'info, Stream>,
+    #[account(
+        init,
+        payer = employer,
+        seeds = [b"receivable", stream.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = stream,
+        mint::freeze_authority = stream,
+    )]
+    pub receivable_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = employer,
+        associated_token::mint = receivable_mint,
+        associated_token::authority = freelancer,
+    )]
+    pub freelancer_receivable_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub employer: Signer<'info>,
+    /// CHECK: Matched against `stream.freelancer`; receives the receivable token
+    pub freelancer: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+pub fn handler(ctx: Context<MintReceivable>) -> Result<()> {
+    let stream = *ctx.accounts.stream.load()?;
+    require!(stream.receivable_mint().is_none(), StreamVaultError::ReceivableAlreadyMinted);
+    require!(
+        stream.recipients().len() == 1 && stream.recipients()[0].recipient == stream.freelancer,
+        StreamVaultError::ReceivableRequiresSingleRecipient
+    );
+    require!(stream.withdrawn_amount == 0, StreamVaultError::InvalidStreamStatus);
+    let employer_key = stream.employer;
+    let stream_id_bytes = stream.stream_id.to_le_bytes();
+    let bump_array = [stream.bump];
+    let seeds = &[
+        b"stream".as_ref(),
+        employer_key.as_ref(),
+        &stream_id_bytes,
+        &bump_array,
+    ][..];
+    let signer_seeds = &[seeds];
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.receivable_mint.to_account_info(),
+        to: ctx.accounts.freelancer_receivable_account.to_account_info(),
+        authority: ctx.accounts.stream.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+    token::mint_to(cpi_ctx, 1)?;
+    let mut stream = ctx.accounts.stream.load_mut()?;
+    stream.set_receivable_mint(Some(ctx.accounts.receivable_mint.key()));
+    msg!(
+        "Stream {} receivable tokenized: mint={}, holder={}",
+        stream.stream_id,
+        ctx.accounts.receivable_mint.key(),
+        ctx.accounts.freelancer.key()
+    );
+    Ok(())
+}
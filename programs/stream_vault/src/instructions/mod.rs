@@ -4,9 +4,39 @@ pub mod withdraw_streamed;
 pub mod pause_stream;
 pub mod resume_stream;
 pub mod close_stream;
+pub mod cancel_stream;
+pub mod configure_multisig;
+pub mod create_proposal;
+pub mod approve_proposal;
+pub mod execute_proposal;
+pub mod set_stream_manager;
+pub mod initialize_stream_config;
+pub mod update_stream_config;
+pub mod quote_withdrawal;
+pub mod mint_receivable;
+pub mod configure_renewal;
+pub mod set_renewal_opt_out;
+pub mod roll_stream;
+pub mod migrate_stream_custody;
+pub mod migrate_stream;
 pub use create_stream::*;
 pub use deposit_to_escrow::*;
 pub use withdraw_streamed::*;
 pub use pause_stream::*;
 pub use resume_stream::*;
-pub use close_stream::*;
\ No newline at end of file
+pub use close_stream::*;
+pub use cancel_stream::*;
+pub use configure_multisig::*;
+pub use create_proposal::*;
+pub use approve_proposal::*;
+pub use execute_proposal::*;
+pub use set_stream_manager::*;
+pub use initialize_stream_config::*;
+pub use update_stream_config::*;
+pub use quote_withdrawal::*;
+pub use mint_receivable::*;
+pub use configure_renewal::*;
+pub use set_renewal_opt_out::*;
+pub use roll_stream::*;
+pub use migrate_stream_custody::*;
+pub use migrate_stream::*;
\ No newline at end of file
@@ -1,29 +1,42 @@
 use anchor_lang::prelude::*;
 use crate::state::Stream;
-use crate::utils::get_current_time;
 use crate::errors::StreamVaultError;
 use crate::constants::MAX_PAUSE_RESUME_COUNT;
 #[derive(Accounts)]
 pub struct PauseStream<'info> {
     #[account(
         mut,
-        seeds = [b"stream", employer.key().as_ref(), stream.stream_id.to_le_bytes().as_ref()],
-        bump = stream.bump,
-        has_one = employer,
+        seeds = [b"stream", stream.load()?.employer.as_ref(), stream.load()?.stream_id.to_le_bytes().as_ref()],
+        bump = stream.load()?.bump,
+        constraint = stream.load()?.can_pause_resume(&authority.key()) @ StreamVaultError::OnlyEmployerCanPauseResume,
     )]
-    pub stream: Account<'info, Stream>,
+    pub stream: AccountLoader<'info, Stream>,
     #[account(mut)]
-    pub employer: Signer<'info>,
+    pub authority: Signer<'info>,
 }
 pub fn handler(ctx: Context<PauseStream>) -> Result<()> {
-    let stream = &mut ctx.accounts.stream;
-    let current_time = get_current_time()?;
+    let stream = &mut *ctx.accounts.stream.load_mut()?;
+    require!(
+        !stream.needs_proposal(&ctx.accounts.authority.key()),
+        StreamVaultError::MultisigRequired
+    );
+    let current_time = stream.current_time()?;
     let current_slot = Clock::get()?.slot;
+    apply_pause(stream, current_time, current_slot)?;
+    msg!(
+        "Stream paused: ID={}, authority={}, pause_time={}",
+        stream.stream_id,
+        ctx.accounts.authority.key(),
+        stream.pause_time().unwrap_or(-1)
+    );
+    Ok(())
+}
+pub fn apply_pause(stream: &mut Stream, current_time: i64, current_slot: u64) -> Result<()> {
     require!(
         stream.last_operation_slot != current_slot,
         StreamVaultError::ConcurrentOperation
     );
-    if !stream.is_funded {
+    if !stream.is_funded() {
         return Err(error!(StreamVaultError::StreamNotFunded));
     }
     if stream.should_auto_close(current_time)? {
@@ -37,11 +50,5 @@ pub fn handler(ctx: Context<PauseStream>) -> Result<()> {
         .checked_add(1)
         .ok_or(error!(StreamVaultError::MathOverflow))?;
     stream.last_operation_slot = current_slot;
-    msg!(
-        "Stream paused: ID={}, employer={}, pause_time={}",
-        stream.stream_id,
-        ctx.accounts.employer.key(),
-        stream.pause_time.unwrap_or(-1)
-    );
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use crate::state::{Stream, FeeMode, WithdrawalQuote};
+use crate::errors::StreamVaultError;
+use crate::utils::round_amount_for_precision;
+#[derive(Accounts)]
+pub struct QuoteWithdrawal<'info> {
+    #[account(
+        seeds = [b"stream", stream.load()?.employer.as_ref(), stream.load()?.stream_id.to_le_bytes().as_ref()],
+        bump = stream.load()?.bump,
+        has_one = mint,
+    )]
+    pub stream: AccountLoader<'info, Stream>,
+    pub mint: Account<'info, Mint>,
+    pub receivable_token_account: Option<Account<'info, TokenAccount>>,
+}
+pub fn handler(ctx: Context<QuoteWithdrawal>, recipient: Pubkey) -> Result<WithdrawalQuote> {
+    let stream = ctx.accounts.stream.load()?;
+    let recipient_index = stream.authorize_withdrawal(
+        &recipient,
+        ctx.accounts.receivable_token_account.as_deref(),
+    )?;
+    let current_time = stream.current_time()?;
+    let withdrawable = stream.calculate_recipient_withdrawable_amount(recipient_index, current_time)?;
+    let gross_amount = round_amount_for_precision(withdrawable, ctx.accounts.mint.decimals);
+    let fee_amount = stream.calculate_fee(gross_amount, FeeMode::OnWithdrawal)?;
+    let net_amount = gross_amount
+        .checked_sub(fee_amount)
+        .ok_or(error!(StreamVaultError::MathOverflow))?;
+    Ok(WithdrawalQuote {
+        gross_amount,
+        fee_amount,
+        net_amount,
+    })
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::Stream;
+use crate::errors::StreamVaultError;
+use crate::vault_cpi::EscrowAccounts;
+#[derive(Accounts)]
+pub struct RollStream<'info> {
+    #[account(
+        mut,
+        seeds = [b"stream", stream.load()?.employer.as_ref(), stream.load()?.stream_id.to_le_bytes().as_ref()],
+        bump = stream.load()?.bump,
+        has_one = vault,
+    )]
+    pub stream: AccountLoader<'info, Stream>,
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = employer_token_account.owner == stream.load()?.employer @ StreamVaultError::Unauthorized,
+        constraint = employer_token_account.mint == stream.load()?.mint @ StreamVaultError::InvalidMint,
+    )]
+    pub employer_token_account: Option<Account<'info, TokenAccount>>,
+    pub cranker: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: Matched against `stream.employer`; receives the escrow's rent when it is closed
+    #[account(mut, address = stream.load()?.employer @ StreamVaultError::Unauthorized)]
+    pub employer: Option<UncheckedAccount<'info>>,
+    /// CHECK: Matched against `stream.escrow`; validated by the vault program
+    #[account(mut)]
+    pub escrow_vault: Option<UncheckedAccount<'info>>,
+    /// CHECK: Validated by the vault program
+    #[account(mut)]
+    pub escrow_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: Must be the vault program
+    pub vault_program: Option<UncheckedAccount<'info>>,
+}
+pub fn handler(ctx: Context<RollStream>) -> Result<()> {
+    let stream = *ctx.accounts.stream.load()?;
+    let current_time = stream.current_time()?;
+    require!(stream.renewal().is_some(), StreamVaultError::RenewalNotEnabled);
+    require!(stream.is_funded(), StreamVaultError::StreamNotFunded);
+    require!(stream.is_ended(current_time), StreamVaultError::StreamNotEnded);
+    if !stream.can_renew() {
+        ctx.accounts.stream.load_mut()?.set_renewal(None);
+        msg!("Stream {} renewal stopped: opted out or period limit reached", stream.stream_id);
+        return Ok(());
+    }
+
+    let employer_key = stream.employer;
+    let stream_id_bytes = stream.stream_id.to_le_bytes();
+    let bump_array = [stream.bump];
+    let seeds = &[
+        b"stream".as_ref(),
+        employer_key.as_ref(),
+        &stream_id_bytes,
+        &bump_array,
+    ][..];
+    let signer_seeds = &[seeds];
+
+    // The escrow only covers the first period's schedule, so a renewing stream pulls
+    // what is left into its own vault, closes the escrow and continues self-custodied.
+    let escrow = EscrowAccounts::load(
+        stream.escrow(),
+        ctx.accounts.escrow_vault.as_ref(),
+        ctx.accounts.escrow_token_account.as_ref(),
+        ctx.accounts.vault_program.as_ref(),
+    )?;
+    if let Some(escrow) = escrow {
+        let (Some(employer), Some(employer_token_account)) = (
+            ctx.accounts.employer.as_ref(),
+            ctx.accounts.employer_token_account.as_ref(),
+        ) else {
+            return Err(error!(StreamVaultError::MissingEscrowAccounts));
+        };
+        let escrow_balance = token::accessor::amount(&escrow.escrow_token_account)?;
+        if escrow_balance > 0 {
+            escrow.release_to_stream(
+                ctx.accounts.stream.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                escrow_balance,
+                signer_seeds,
+            )?;
+            ctx.accounts.vault.reload()?;
+        }
+        escrow.close(
+            ctx.accounts.stream.to_account_info(),
+            employer.to_account_info(),
+            employer_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            signer_seeds,
+        )?;
+        ctx.accounts.stream.load_mut()?.set_escrow(None);
+        msg!("Stream {} moved {} tokens out of vault custody", stream.stream_id, escrow_balance);
+    }
+
+    // Whatever the vault holds beyond outstanding earnings pre-funds the next period;
+    // any shortfall is pulled from the employer through a delegated allowance.
+    let outstanding = stream.total_payable().saturating_sub(stream.withdrawn_amount);
+    let prefunded = ctx.accounts.vault.amount.saturating_sub(outstanding);
+    let shortfall = stream.total_amount.saturating_sub(prefunded);
+    if shortfall > 0 {
+        let allowance = ctx.accounts.employer_token_account
+            .as_ref()
+            .filter(|account| account.delegate == Some(ctx.accounts.stream.key()).into())
+            .map(|account| account.delegated_amount.min(account.amount))
+            .unwrap_or(0);
+        if allowance < shortfall {
+            ctx.accounts.stream.load_mut()?.set_renewal(None);
+            msg!(
+                "Stream {} renewal stopped: next period underfunded by {}",
+                stream.stream_id,
+                shortfall - allowance
+            );
+            return Ok(());
+        }
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.employer_token_account.as_ref().unwrap().to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.stream.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, shortfall)?;
+    }
+
+    let mut stream = ctx.accounts.stream.load_mut()?;
+    stream.roll_period()?;
+    stream.last_operation_slot = Clock::get()?.slot;
+    msg!(
+        "Stream {} rolled: start={}, end={}, carried_over={}, pulled_from_allowance={}",
+        stream.stream_id,
+        stream.start_time,
+        stream.end_time,
+        stream.carried_over_amount,
+        shortfall
+    );
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::Stream;
+use crate::errors::StreamVaultError;
+#[derive(Accounts)]
+pub struct SetRenewalOptOut<'info> {
+    #[account(
+        mut,
+        seeds = [b"stream", stream.load()?.employer.as_ref(), stream.load()?.stream_id.to_le_bytes().as_ref()],
+        bump = stream.load()?.bump,
+    )]
+    pub stream: AccountLoader<'info, Stream>,
+    pub authority: Signer<'info>,
+}
+pub fn handler(ctx: Context<SetRenewalOptOut>, opted_out: bool) -> Result<()> {
+    let mut stream = ctx.accounts.stream.load_mut()?;
+    let authority = ctx.accounts.authority.key();
+    let is_employer = authority == stream.employer;
+    let is_recipient = stream.recipient_index(&authority).is_some();
+    require!(is_employer || is_recipient, StreamVaultError::Unauthorized);
+    let renewal = stream.renewal_mut()
+        .ok_or(error!(StreamVaultError::RenewalNotEnabled))?;
+    if is_employer {
+        renewal.set_employer_opted_out(opted_out);
+    }
+    if is_recipient {
+        renewal.set_recipient_opted_out(opted_out);
+    }
+    msg!(
+        "Stream {} renewal opt-out set to {} by {}",
+        stream.stream_id,
+        opted_out,
+        authority
+    );
+    Ok(())
+}
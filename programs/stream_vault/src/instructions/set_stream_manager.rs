@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::state::Stream;
+use crate::errors::StreamVaultError;
+#[derive(Accounts)]
+pub struct SetStreamManager<'info> {
+    #[account(
+        mut,
+        seeds = [b"stream", employer.key().as_ref(), stream.load()?.stream_id.to_le_bytes().as_ref()],
+        bump = stream.load()?.bump,
+        has_one = employer,
+    )]
+    pub stream: AccountLoader<'info, Stream>,
+    pub employer: Signer<'info>,
+}
+pub fn handler(ctx: Context<SetStreamManager>, manager: Option<Pubkey>) -> Result<()> {
+    let mut stream = ctx.accounts.stream.load_mut()?;
+    require!(stream.multisig().is_none(), StreamVaultError::MultisigRequired);
+    stream.update_manager(manager)?;
+    msg!(
+        "Stream {} manager set to {:?}",
+        stream.stream_id,
+        stream.manager()
+    );
+    Ok(())
+}
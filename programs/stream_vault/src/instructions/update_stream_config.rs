@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::{StreamConfig, FeeMode, StartTimePolicy};
+use super::initialize_stream_config::{validate_fee_config, validate_slot_duration};
+#[derive(Accounts)]
+pub struct UpdateStreamConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"stream_config"],
+        bump = config.bump,
+        has_one = authority,
+    )]
+    pub config: Account<'info, StreamConfig>,
+    pub authority: Signer<'info>,
+    /// CHECK: The fee recipient only owns the token accounts that receive protocol fees
+    pub fee_recipient: UncheckedAccount<'info>,
+}
+pub fn handler(
+    ctx: Context<UpdateStreamConfig>,
+    fee_basis_points: u16,
+    fee_mode: FeeMode,
+    fee_exempt: Vec<Pubkey>,
+    start_time_policy: StartTimePolicy,
+    max_slot_duration_ms: u32,
+) -> Result<()> {
+    validate_fee_config(fee_basis_points, &fee_exempt)?;
+    validate_slot_duration(max_slot_duration_ms)?;
+    start_time_policy.validate()?;
+    let config = &mut ctx.accounts.config;
+    config.fee_basis_points = fee_basis_points;
+    config.fee_recipient = ctx.accounts.fee_recipient.key();
+    config.fee_mode = fee_mode;
+    config.fee_exempt = fee_exempt;
+    config.start_time_policy = start_time_policy;
+    config.max_slot_duration_ms = max_slot_duration_ms;
+    msg!(
+        "Stream config updated: fee={} bps, mode={:?}, recipient={}",
+        fee_basis_points,
+        fee_mode,
+        config.fee_recipient
+    );
+    Ok(())
+}
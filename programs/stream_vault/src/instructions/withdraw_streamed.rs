@@ -1,87 +1,180 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
-use crate::state::Stream;
+use crate::state::{Stream, FeeMode};
 use crate::errors::StreamVaultError;
-use crate::utils::{get_current_time, is_dust_amount, round_amount_for_precision};
+use crate::utils::{is_dust_amount, round_amount_for_precision};
 use crate::constants::get_min_withdrawal_amount;
+use crate::vault_cpi::EscrowAccounts;
 #[derive(Accounts)]
 pub struct WithdrawStreamed<'info> {
     #[account(
         mut,
-        seeds = [b"stream", stream.employer.as_ref(), stream.stream_id.to_le_bytes().as_ref()],
-        bump = stream.bump,
-        has_one = freelancer,
+        seeds = [b"stream", stream.load()?.employer.as_ref(), stream.load()?.stream_id.to_le_bytes().as_ref()],
+        bump = stream.load()?.bump,
         has_one = vault,
         has_one = mint,
     )]
-    pub stream: Account<'info, Stream>,
+    pub stream: AccountLoader<'info, Stream>,
     #[account(mut)]
     pub vault: Account<'info, TokenAccount>,
     #[account(
         mut,
         constraint = freelancer_token_account.owner == freelancer.key() @ StreamVaultError::Unauthorized,
-        constraint = freelancer_token_account.mint == stream.mint @ StreamVaultError::InvalidMint,
+        constraint = freelancer_token_account.mint == stream.load()?.mint @ StreamVaultError::InvalidMint,
     )]
     pub freelancer_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = destination.mint == stream.load()?.mint @ StreamVaultError::InvalidMint,
+    )]
+    pub destination: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = fee_token_account.owner == stream.load()?.fee_recipient @ StreamVaultError::InvalidFeeAccount,
+        constraint = fee_token_account.mint == stream.load()?.mint @ StreamVaultError::InvalidFeeAccount,
+    )]
+    pub fee_token_account: Option<Account<'info, TokenAccount>>,
+    pub receivable_token_account: Option<Account<'info, TokenAccount>>,
     #[account(mut)]
     pub freelancer: Signer<'info>,
     pub mint: Account<'info, Mint>,
     pub token_program: Program<'info, Token>,
+    /// CHECK: Matched against `stream.escrow`; validated by the vault program
+    #[account(mut)]
+    pub escrow_vault: Option<UncheckedAccount<'info>>,
+    /// CHECK: Validated by the vault program
+    #[account(mut)]
+    pub escrow_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: Must be the vault program
+    pub vault_program: Option<UncheckedAccount<'info>>,
 }
 
-pub fn handler(ctx: Context<WithdrawStreamed>) -> Result<()> {
+pub fn handler(ctx: Context<WithdrawStreamed>, amount: Option<u64>) -> Result<()> {
     // Take all immutable borrows first
-    let stream_info = &ctx.accounts.stream;
-    if !stream_info.is_funded {
+    let stream_info = *ctx.accounts.stream.load()?;
+    if !stream_info.is_funded() {
         return Err(error!(StreamVaultError::StreamNotFunded));
     }
-    if stream_info.is_paused {
+    if stream_info.is_paused() {
         return Err(error!(StreamVaultError::StreamPaused));
     }
-    let current_time = get_current_time()?;
+    let current_time = stream_info.current_time()?;
     if stream_info.start_time > current_time {
         return Err(error!(StreamVaultError::StreamNotStarted));
     }
-    let withdrawable = stream_info.calculate_withdrawable_amount(current_time)?;
+    let recipient_index = stream_info.authorize_withdrawal(
+        &ctx.accounts.freelancer.key(),
+        ctx.accounts.receivable_token_account.as_deref(),
+    )?;
+    let withdrawable = stream_info.calculate_recipient_withdrawable_amount(recipient_index, current_time)?;
     if !stream_info.has_remaining_funds() {
         return Err(error!(StreamVaultError::NoFundsAvailable));
     }
     
     // Get stream data needed for seeds before mutable borrow
-    let stream_pubkey = stream_info.key();
+    let employer_key = stream_info.employer;
+    let stream_id_bytes = stream_info.stream_id.to_le_bytes();
     let bump = stream_info.bump;
-    let signer: &[&[&[u8]]] = &[&[b"stream", stream_pubkey.as_ref(), &[bump]]];
+    let signer: &[&[&[u8]]] = &[&[b"stream", employer_key.as_ref(), &stream_id_bytes, &[bump]]];
     
     // Now perform mutable operations
     let withdrawable_amount = round_amount_for_precision(withdrawable, ctx.accounts.mint.decimals);
+    let withdrawable_amount = match amount {
+        Some(requested) => {
+            require!(
+                requested > 0 && requested <= withdrawable_amount,
+                StreamVaultError::InvalidAmount
+            );
+            requested
+        },
+        None => withdrawable_amount,
+    };
     
     // Check minimum withdrawal before creating the CPI context
     if withdrawable_amount < get_min_withdrawal_amount(&ctx.accounts.mint.key()) {
         // Now we can take a mutable reference since we're done with immutable borrows
-        let stream = &mut ctx.accounts.stream;
+        let mut stream = ctx.accounts.stream.load_mut()?;
         stream.accumulated_dust = stream.accumulated_dust
             .checked_add(withdrawable_amount)
             .ok_or(error!(StreamVaultError::MathOverflow))?;
         return Err(error!(StreamVaultError::BelowMinimumWithdrawal));
     }
 
+    // Route to the explicit destination when one is supplied
+    let destination = match ctx.accounts.destination.as_ref() {
+        Some(destination) => destination.to_account_info(),
+        None => ctx.accounts.freelancer_token_account.to_account_info(),
+    };
+
+    // Streams in vault custody pull the payout into the stream vault first
+    let escrow = EscrowAccounts::load(
+        stream_info.escrow(),
+        ctx.accounts.escrow_vault.as_ref(),
+        ctx.accounts.escrow_token_account.as_ref(),
+        ctx.accounts.vault_program.as_ref(),
+    )?;
+    if let Some(escrow) = escrow {
+        let shortfall = withdrawable_amount.saturating_sub(ctx.accounts.vault.amount);
+        if shortfall > 0 {
+            escrow.release_to_stream(
+                ctx.accounts.stream.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                shortfall,
+                signer,
+            )?;
+            ctx.accounts.vault.reload()?;
+        }
+        require!(
+            ctx.accounts.vault.amount >= withdrawable_amount,
+            StreamVaultError::InsufficientFunds
+        );
+    }
+
+    let fee_amount = stream_info.calculate_fee(withdrawable_amount, FeeMode::OnWithdrawal)?;
+    let net_amount = withdrawable_amount
+        .checked_sub(fee_amount)
+        .ok_or(error!(StreamVaultError::MathOverflow))?;
+
     // Prepare CPI accounts
     let cpi_accounts = Transfer {
         from: ctx.accounts.vault.to_account_info(),
-        to: ctx.accounts.freelancer_token_account.to_account_info(),
+        to: destination.clone(),
         authority: ctx.accounts.stream.to_account_info(),
     };
 
     // Perform the transfer
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-    token::transfer(cpi_ctx, withdrawable_amount)?;
+    token::transfer(cpi_ctx, net_amount)?;
+
+    // Route the protocol fee to the fee recipient
+    if fee_amount > 0 {
+        let fee_token_account = ctx.accounts.fee_token_account
+            .as_ref()
+            .ok_or(error!(StreamVaultError::InvalidFeeAccount))?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: fee_token_account.to_account_info(),
+            authority: ctx.accounts.stream.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer);
+        token::transfer(cpi_ctx, fee_amount)?;
+    }
 
     // Update stream state after the transfer
-    let stream = &mut ctx.accounts.stream;
-    stream.withdrawn_amount = stream.withdrawn_amount
-        .checked_add(withdrawable_amount)
+    let mut stream = ctx.accounts.stream.load_mut()?;
+    stream.record_recipient_withdrawal(recipient_index, withdrawable_amount)?;
+    stream.fees_paid = stream.fees_paid
+        .checked_add(fee_amount)
         .ok_or(error!(StreamVaultError::MathOverflow))?;
     stream.last_operation_slot = Clock::get()?.slot;
+    msg!(
+        "Withdrew {} tokens from stream {} to {} (fee={})",
+        net_amount,
+        stream.stream_id,
+        destination.key(),
+        fee_amount
+    );
     Ok(())
 }
\ No newline at end of file
@@ -1,12 +1,13 @@
 use anchor_lang::prelude::*;
-mod constants;
-mod errors;
+pub mod constants;
+pub mod errors;
 mod instructions;
-mod state;
-mod strategies;
+pub mod state;
+pub mod strategies;
 mod utils;
+pub mod vault_cpi;
 use instructions::*;
-use state::StreamDuration;
+use state::{StreamDuration, RecipientShare, StreamAction, FeeMode, WithdrawalQuote, StartTimePolicy};
 use strategies::AccelerationType;
 declare_id!("Cp34F2Ho7oCetL6UkfFDLyGfvh5FcPuRiT1DSHiEAVbF");
 #[program]
@@ -19,14 +20,15 @@ pub mod stream_vault {
         stream_duration: StreamDuration,
         total_amount: u64,
         acceleration_type: Option<AccelerationType>,
+        recipients: Option<Vec<RecipientShare>>,
     ) -> Result<()> {
-        instructions::create_stream::handler(ctx, stream_id, start_time, stream_duration, total_amount, acceleration_type)
+        instructions::create_stream::handler(ctx, stream_id, start_time, stream_duration, total_amount, acceleration_type, recipients)
     }
     pub fn deposit_to_escrow(ctx: Context<DepositToEscrow>, amount: u64) -> Result<()> {
         instructions::deposit_to_escrow::handler(ctx, amount)
     }
-    pub fn withdraw_streamed(ctx: Context<WithdrawStreamed>) -> Result<()> {
-        instructions::withdraw_streamed::handler(ctx)
+    pub fn withdraw_streamed(ctx: Context<WithdrawStreamed>, amount: Option<u64>) -> Result<()> {
+        instructions::withdraw_streamed::handler(ctx, amount)
     }
     pub fn pause_stream(ctx: Context<PauseStream>) -> Result<()> {
         instructions::pause_stream::handler(ctx)
@@ -34,7 +36,66 @@ pub mod stream_vault {
     pub fn resume_stream(ctx: Context<ResumeStream>) -> Result<()> {
         instructions::resume_stream::handler(ctx)
     }
-    pub fn close_stream(ctx: Context<CloseStream>) -> Result<()> {
+    pub fn close_stream<'info>(ctx: Context<'_, '_, 'info, 'info, CloseStream<'info>>) -> Result<()> {
         instructions::close_stream::handler(ctx)
     }
+    pub fn cancel_stream<'info>(ctx: Context<'_, '_, 'info, 'info, CancelStream<'info>>) -> Result<()> {
+        instructions::cancel_stream::handler(ctx)
+    }
+    pub fn configure_multisig(ctx: Context<ConfigureMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        instructions::configure_multisig::handler(ctx, signers, threshold)
+    }
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: StreamAction, lifetime: i64) -> Result<()> {
+        instructions::create_proposal::handler(ctx, action, lifetime)
+    }
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        instructions::approve_proposal::handler(ctx)
+    }
+    pub fn execute_proposal<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>) -> Result<()> {
+        instructions::execute_proposal::handler(ctx)
+    }
+    pub fn set_stream_manager(ctx: Context<SetStreamManager>, manager: Option<Pubkey>) -> Result<()> {
+        instructions::set_stream_manager::handler(ctx, manager)
+    }
+    pub fn initialize_stream_config(
+        ctx: Context<InitializeStreamConfig>,
+        fee_basis_points: u16,
+        fee_mode: FeeMode,
+        fee_exempt: Vec<Pubkey>,
+        start_time_policy: StartTimePolicy,
+        max_slot_duration_ms: u32,
+    ) -> Result<()> {
+        instructions::initialize_stream_config::handler(ctx, fee_basis_points, fee_mode, fee_exempt, start_time_policy, max_slot_duration_ms)
+    }
+    pub fn update_stream_config(
+        ctx: Context<UpdateStreamConfig>,
+        fee_basis_points: u16,
+        fee_mode: FeeMode,
+        fee_exempt: Vec<Pubkey>,
+        start_time_policy: StartTimePolicy,
+        max_slot_duration_ms: u32,
+    ) -> Result<()> {
+        instructions::update_stream_config::handler(ctx, fee_basis_points, fee_mode, fee_exempt, start_time_policy, max_slot_duration_ms)
+    }
+    pub fn quote_withdrawal(ctx: Context<QuoteWithdrawal>, recipient: Pubkey) -> Result<WithdrawalQuote> {
+        instructions::quote_withdrawal::handler(ctx, recipient)
+    }
+    pub fn mint_receivable(ctx: Context<MintReceivable>) -> Result<()> {
+        instructions::mint_receivable::handler(ctx)
+    }
+    pub fn configure_renewal(ctx: Context<ConfigureRenewal>, max_periods: u32) -> Result<()> {
+        instructions::configure_renewal::handler(ctx, max_periods)
+    }
+    pub fn set_renewal_opt_out(ctx: Context<SetRenewalOptOut>, opted_out: bool) -> Result<()> {
+        instructions::set_renewal_opt_out::handler(ctx, opted_out)
+    }
+    pub fn roll_stream(ctx: Context<RollStream>) -> Result<()> {
+        instructions::roll_stream::handler(ctx)
+    }
+    pub fn migrate_stream_custody(ctx: Context<MigrateStreamCustody>) -> Result<()> {
+        instructions::migrate_stream_custody::handler(ctx)
+    }
+    pub fn migrate_stream(ctx: Context<MigrateStream>) -> Result<()> {
+        instructions::migrate_stream::handler(ctx)
+    }
 }
\ No newline at end of file
@@ -1,59 +1,215 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_spl::token::TokenAccount;
+use bytemuck::Zeroable;
 use crate::strategies::{ExponentialStreamingStrategy, StreamingStrategy, StreamingContext, AccelerationType};
-#[account]
+use crate::constants::{MAX_STREAM_RECIPIENTS, BASIS_POINTS_DENOMINATOR, MAX_MULTISIG_SIGNERS, MAX_FEE_EXEMPT_ACCOUNTS, STREAM_VERSION};
+use crate::errors::StreamVaultError;
+use crate::utils::get_guarded_time;
+// Fixed-size layout read in place. Optional keys are stored as the default
+// pubkey when unset and flags as 0/1 bytes; use the accessor methods rather
+// than the raw fields.
+#[account(zero_copy)]
 pub struct Stream {
+    pub version: u8,
+    pub bump: u8,
+    pub is_paused: u8,
+    pub is_funded: u8,
+    pub pause_resume_count: u8,
+    pub acceleration_type: u8,
+    pub fee_mode: u8,
+    pub recipient_count: u8,
+    pub fee_basis_points: u16,
+    pub has_renewal: u8,
+    pub start_time_policy: u8,
+    pub max_slot_duration_ms: u32,
     pub employer: Pubkey,
     pub freelancer: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub multisig: Pubkey,
+    pub manager: Pubkey,
+    pub receivable_mint: Pubkey,
+    pub escrow: Pubkey,
     pub stream_id: u64,
     pub start_time: i64,
     pub end_time: i64,
     pub total_amount: u64,
     pub withdrawn_amount: u64,
-    pub is_paused: bool,
-    pub pause_time: Option<i64>,
+    pub pause_time: i64,
     pub total_paused_duration: i64,
-    pub pause_resume_count: u8,
     pub last_operation_slot: u64,
     pub accumulated_dust: u64,
-    pub mint: Pubkey,
-    pub vault: Pubkey,
-    pub acceleration_type: AccelerationType,
-    pub bump: u8,
-    pub is_funded: bool,
+    pub fees_paid: u64,
+    pub carried_over_amount: u64,
+    pub recipient_slots: [StreamRecipient; MAX_STREAM_RECIPIENTS],
+    pub renewal: StreamRenewal,
+}
+fn optional_key(key: Pubkey) -> Option<Pubkey> {
+    if key == Pubkey::default() {
+        None
+    } else {
+        Some(key)
+    }
 }
 impl Stream {
-    pub const LEN: usize = 8 + 
-        32 + 
-        32 + 
-        8 + 
-        8 + 
-        8 + 
-        8 + 
-        8 + 
-        1 + 
-        1 + 8 + 
-        8 + 
-        1 + 
-        8 + 
-        8 + 
-        32 + 
-        32 + 
-        1 + 
-        1;
+    pub const LEN: usize = 8 + std::mem::size_of::<Stream>();
+
+    // Decodes a stream account of any supported layout, upgrading older layouts in
+    // memory. Returns the stream together with the version it was stored as.
+    pub fn from_versioned_bytes(data: &[u8]) -> Result<(Self, u8)> {
+        require!(
+            data.len() >= 9 && data[..8] == Stream::DISCRIMINATOR[..],
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        if data.len() == StreamV1::LEN {
+            let legacy = StreamV1::deserialize(&mut &data[8..])?;
+            return Ok((legacy.upgrade().upgrade()?, 1));
+        }
+        match data[8] {
+            2 => {
+                let legacy = StreamV2::deserialize(&mut &data[8..])?;
+                Ok((legacy.upgrade()?, 2))
+            },
+            STREAM_VERSION => {
+                require!(
+                    data.len() >= Stream::LEN,
+                    anchor_lang::error::ErrorCode::AccountDidNotDeserialize
+                );
+                Ok((bytemuck::pod_read_unaligned(&data[8..Stream::LEN]), STREAM_VERSION))
+            },
+            _ => Err(error!(StreamVaultError::UnsupportedAccountVersion)),
+        }
+    }
 
-    pub const LEN_CALCULATION: usize = 8 + 
-        32 + 32 + 8 + 1 + 1 + 8 + 8 + 1 + 8 + 8 + 32 + 32 + 1 + 1;
+    pub fn is_paused(&self) -> bool {
+        self.is_paused != 0
+    }
+    pub fn is_funded(&self) -> bool {
+        self.is_funded != 0
+    }
+    pub fn set_funded(&mut self, funded: bool) {
+        self.is_funded = funded as u8;
+    }
+    // Streams created under a backdating policy may still be funded after they start.
+    pub fn allows_late_funding(&self) -> bool {
+        self.start_time_policy != 0
+    }
+    pub fn catches_up(&self) -> bool {
+        self.start_time_policy == 2
+    }
+    pub fn set_start_time_policy(&mut self, policy: StartTimePolicy) {
+        self.start_time_policy = match policy {
+            StartTimePolicy::Strict => 0,
+            StartTimePolicy::Backdate { .. } => 1,
+            StartTimePolicy::BackdateWithCatchUp { .. } => 2,
+        };
+    }
+    // Time used for accrual, capped by the slot/time guard when it is enabled.
+    pub fn current_time(&self) -> Result<i64> {
+        get_guarded_time(self.max_slot_duration_ms)
+    }
+    pub fn pause_time(&self) -> Option<i64> {
+        if self.is_paused() {
+            Some(self.pause_time)
+        } else {
+            None
+        }
+    }
+    pub fn acceleration_type(&self) -> AccelerationType {
+        match self.acceleration_type {
+            2 => AccelerationType::Quadratic,
+            3 => AccelerationType::Cubic,
+            _ => AccelerationType::Linear,
+        }
+    }
+    pub fn set_acceleration_type(&mut self, acceleration_type: AccelerationType) {
+        self.acceleration_type = acceleration_type as u8;
+    }
+    pub fn fee_mode(&self) -> FeeMode {
+        match self.fee_mode {
+            0 => FeeMode::OnCreation,
+            _ => FeeMode::OnWithdrawal,
+        }
+    }
+    pub fn set_fee_mode(&mut self, fee_mode: FeeMode) {
+        self.fee_mode = fee_mode as u8;
+    }
+    pub fn multisig(&self) -> Option<Pubkey> {
+        optional_key(self.multisig)
+    }
+    pub fn set_multisig(&mut self, multisig: Option<Pubkey>) {
+        self.multisig = multisig.unwrap_or_default();
+    }
+    pub fn manager(&self) -> Option<Pubkey> {
+        optional_key(self.manager)
+    }
+    pub fn set_manager(&mut self, manager: Option<Pubkey>) {
+        self.manager = manager.unwrap_or_default();
+    }
+    pub fn update_manager(&mut self, manager: Option<Pubkey>) -> Result<()> {
+        require!(manager != Some(self.employer), StreamVaultError::InvalidManager);
+        self.set_manager(manager);
+        Ok(())
+    }
+    pub fn receivable_mint(&self) -> Option<Pubkey> {
+        optional_key(self.receivable_mint)
+    }
+    pub fn set_receivable_mint(&mut self, receivable_mint: Option<Pubkey>) {
+        self.receivable_mint = receivable_mint.unwrap_or_default();
+    }
+    pub fn escrow(&self) -> Option<Pubkey> {
+        optional_key(self.escrow)
+    }
+    pub fn set_escrow(&mut self, escrow: Option<Pubkey>) {
+        self.escrow = escrow.unwrap_or_default();
+    }
+    pub fn renewal(&self) -> Option<StreamRenewal> {
+        if self.has_renewal != 0 {
+            Some(self.renewal)
+        } else {
+            None
+        }
+    }
+    pub fn renewal_mut(&mut self) -> Option<&mut StreamRenewal> {
+        if self.has_renewal != 0 {
+            Some(&mut self.renewal)
+        } else {
+            None
+        }
+    }
+    pub fn set_renewal(&mut self, renewal: Option<StreamRenewal>) {
+        self.has_renewal = renewal.is_some() as u8;
+        self.renewal = renewal.unwrap_or_default();
+    }
+    pub fn recipients(&self) -> &[StreamRecipient] {
+        &self.recipient_slots[..self.recipient_count as usize]
+    }
+    pub fn recipients_mut(&mut self) -> &mut [StreamRecipient] {
+        &mut self.recipient_slots[..self.recipient_count as usize]
+    }
+    pub fn set_recipients(&mut self, recipients: &[StreamRecipient]) -> Result<()> {
+        require!(
+            recipients.len() <= MAX_STREAM_RECIPIENTS,
+            StreamVaultError::InvalidRecipientCount
+        );
+        self.recipient_slots = [StreamRecipient::default(); MAX_STREAM_RECIPIENTS];
+        self.recipient_slots[..recipients.len()].copy_from_slice(recipients);
+        self.recipient_count = recipients.len() as u8;
+        Ok(())
+    }
 
     pub fn calculate_earned_amount(&self, current_time: i64) -> Result<u64> {
-        let strategy = ExponentialStreamingStrategy::new(self.acceleration_type);
+        let strategy = ExponentialStreamingStrategy::new(self.acceleration_type());
         strategy.calculate_earned_amount(
             self.total_amount,
             self.start_time,
             self.end_time,
             current_time,
             self.total_paused_duration,
-            self.is_paused,
-            self.pause_time,
+            self.is_paused(),
+            self.pause_time(),
         )
     }
     pub fn calculate_withdrawable_amount(&self, current_time: i64) -> Result<u64> {
@@ -63,11 +219,11 @@ impl Stream {
             self.end_time,
             current_time,
             self.total_paused_duration,
-            self.is_paused,
-            self.pause_time,
+            self.is_paused(),
+            self.pause_time(),
             self.withdrawn_amount,
         );
-        let strategy = ExponentialStreamingStrategy::new(self.acceleration_type);
+        let strategy = ExponentialStreamingStrategy::new(self.acceleration_type());
         strategy.calculate_withdrawable_amount(&ctx)
     }
     pub fn is_ended(&self, current_time: i64) -> bool {
@@ -76,13 +232,13 @@ impl Stream {
     pub fn get_stream_duration(&self) -> Result<i64> {
         self.end_time
             .checked_sub(self.start_time)
-            .ok_or(error!(crate::errors::SprintVaultError::MathOverflow))
+            .ok_or(error!(crate::errors::StreamVaultError::MathOverflow))
     }
     pub fn should_auto_close(&self, current_time: i64) -> Result<bool> {
-        if !self.is_paused {
+        if !self.is_paused() {
             return Ok(false);
         }
-        if let Some(pause_time) = self.pause_time {
+        if let Some(pause_time) = self.pause_time() {
             let current_pause_duration = current_time
                 .checked_sub(pause_time)
                 .ok_or(error!(crate::errors::StreamVaultError::MathOverflow))?;
@@ -94,37 +250,605 @@ impl Stream {
         Ok(false)
     }
     pub fn has_remaining_funds(&self) -> bool {
-        self.withdrawn_amount < self.total_amount
+        self.withdrawn_amount < self.total_payable()
+    }
+    pub fn total_payable(&self) -> u64 {
+        self.total_amount.saturating_add(self.carried_over_amount)
     }
     pub fn is_final_withdrawal(&self, current_time: i64) -> Result<bool> {
         let earned = self.calculate_earned_amount(current_time)?;
         Ok(earned >= self.total_amount)
     }
     pub fn pause(&mut self, current_time: i64) -> Result<()> {
-        if self.is_paused {
-            return Err(error!(crate::errors::SprintVaultError::AlreadyPaused));
+        if self.is_paused() {
+            return Err(error!(crate::errors::StreamVaultError::AlreadyPaused));
         }
-        self.is_paused = true;
-        self.pause_time = Some(current_time);
+        self.is_paused = 1;
+        self.pause_time = current_time;
         Ok(())
     }
     pub fn resume(&mut self, current_time: i64) -> Result<()> {
-        if !self.is_paused {
-            return Err(error!(crate::errors::SprintVaultError::NotPaused));
+        if !self.is_paused() {
+            return Err(error!(crate::errors::StreamVaultError::NotPaused));
         }
-        if let Some(pause_time) = self.pause_time {
-            let pause_duration = current_time
-                .checked_sub(pause_time)
-                .ok_or(error!(crate::errors::StreamVaultError::MathOverflow))?;
-            self.total_paused_duration = self.total_paused_duration
-                .checked_add(pause_duration)
-                .ok_or(error!(crate::errors::StreamVaultError::MathOverflow))?;
+        let pause_duration = current_time
+            .checked_sub(self.pause_time)
+            .ok_or(error!(crate::errors::StreamVaultError::MathOverflow))?;
+        self.total_paused_duration = self.total_paused_duration
+            .checked_add(pause_duration)
+            .ok_or(error!(crate::errors::StreamVaultError::MathOverflow))?;
+        self.is_paused = 0;
+        self.pause_time = 0;
+        Ok(())
+    }
+    pub fn calculate_fee(&self, amount: u64, mode: FeeMode) -> Result<u64> {
+        if self.fee_mode() != mode || self.fee_basis_points == 0 {
+            return Ok(0);
         }
-        self.is_paused = false;
-        self.pause_time = None;
+        let fee = (amount as u128)
+            .checked_mul(self.fee_basis_points as u128)
+            .ok_or(error!(StreamVaultError::MathOverflow))?
+            .checked_div(BASIS_POINTS_DENOMINATOR as u128)
+            .ok_or(error!(StreamVaultError::MathOverflow))?;
+        Ok(fee as u64)
+    }
+    pub fn can_pause_resume(&self, signer: &Pubkey) -> bool {
+        *signer == self.employer || self.manager() == Some(*signer)
+    }
+    // Under a multisig the employer acts through proposals; a manager keeps its
+    // direct pause/resume rights until a proposal removes it.
+    pub fn needs_proposal(&self, signer: &Pubkey) -> bool {
+        self.multisig().is_some() && self.manager() != Some(*signer)
+    }
+    pub fn is_receivable_holder(&self, holder: &Pubkey, token_account: &TokenAccount) -> bool {
+        match self.receivable_mint() {
+            Some(mint) => {
+                token_account.mint == mint &&
+                token_account.owner == *holder &&
+                token_account.amount == 1
+            },
+            None => false,
+        }
+    }
+    pub fn authorize_withdrawal(&self, signer: &Pubkey, receivable: Option<&TokenAccount>) -> Result<usize> {
+        if self.receivable_mint().is_some() {
+            let receivable = receivable.ok_or(error!(StreamVaultError::NotReceivableHolder))?;
+            require!(
+                self.is_receivable_holder(signer, receivable),
+                StreamVaultError::NotReceivableHolder
+            );
+            return Ok(0);
+        }
+        self.recipient_index(signer)
+            .ok_or(error!(StreamVaultError::Unauthorized))
+    }
+    pub fn can_renew(&self) -> bool {
+        match self.renewal() {
+            Some(renewal) => {
+                !renewal.employer_opted_out() &&
+                !renewal.recipient_opted_out() &&
+                renewal.periods_completed < renewal.max_periods
+            },
+            None => false,
+        }
+    }
+    // Starts the next period with the same terms. Everything earned in the
+    // finished period but not yet withdrawn is carried over per recipient.
+    pub fn roll_period(&mut self) -> Result<()> {
+        let duration = self.get_stream_duration()?;
+        let next_start = self.end_time
+            .checked_add(self.total_paused_duration)
+            .ok_or(error!(StreamVaultError::MathOverflow))?;
+        let total_amount = self.total_amount;
+        let mut carried_over_amount = 0u64;
+        for recipient in self.recipients_mut().iter_mut() {
+            let owed = recipient.share_of(total_amount)?
+                .checked_add(recipient.carried_over)
+                .ok_or(error!(StreamVaultError::MathOverflow))?
+                .saturating_sub(recipient.withdrawn_amount);
+            recipient.carried_over = owed;
+            recipient.withdrawn_amount = 0;
+            carried_over_amount = carried_over_amount
+                .checked_add(owed)
+                .ok_or(error!(StreamVaultError::MathOverflow))?;
+        }
+        self.carried_over_amount = carried_over_amount;
+        self.withdrawn_amount = 0;
+        self.start_time = next_start;
+        self.end_time = next_start
+            .checked_add(duration)
+            .ok_or(error!(StreamVaultError::MathOverflow))?;
+        self.is_paused = 0;
+        self.pause_time = 0;
+        self.total_paused_duration = 0;
+        self.pause_resume_count = 0;
+        self.accumulated_dust = 0;
+        if let Some(renewal) = self.renewal_mut() {
+            renewal.periods_completed = renewal.periods_completed
+                .checked_add(1)
+                .ok_or(error!(StreamVaultError::MathOverflow))?;
+        }
+        Ok(())
+    }
+    pub fn recipient_index(&self, recipient: &Pubkey) -> Option<usize> {
+        self.recipients().iter().position(|r| r.recipient == *recipient)
+    }
+    pub fn calculate_recipient_earned_amount(&self, index: usize, current_time: i64) -> Result<u64> {
+        let recipient = self.recipients()
+            .get(index)
+            .ok_or(error!(StreamVaultError::Unauthorized))?;
+        let earned = self.calculate_earned_amount(current_time)?;
+        recipient.share_of(earned)
+    }
+    pub fn calculate_recipient_withdrawable_amount(&self, index: usize, current_time: i64) -> Result<u64> {
+        let earned = self.calculate_recipient_earned_amount(index, current_time)?
+            .checked_add(self.recipients()[index].carried_over)
+            .ok_or(error!(StreamVaultError::MathOverflow))?;
+        Ok(earned.saturating_sub(self.recipients()[index].withdrawn_amount))
+    }
+    pub fn record_recipient_withdrawal(&mut self, index: usize, amount: u64) -> Result<()> {
+        let recipient = self.recipients_mut()
+            .get_mut(index)
+            .ok_or(error!(StreamVaultError::Unauthorized))?;
+        recipient.withdrawn_amount = recipient.withdrawn_amount
+            .checked_add(amount)
+            .ok_or(error!(StreamVaultError::MathOverflow))?;
+        self.withdrawn_amount = self.withdrawn_amount
+            .checked_add(amount)
+            .ok_or(error!(StreamVaultError::MathOverflow))?;
         Ok(())
     }
 }
+// Layout of streams created before accounts carried a version byte. Those
+// accounts were allocated `LEN` bytes, which is one byte short of the full
+// layout, so this size alone identifies them.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StreamV1 {
+    pub employer: Pubkey,
+    pub freelancer: Pubkey,
+    pub stream_id: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub total_amount: u64,
+    pub withdrawn_amount: u64,
+    pub is_paused: bool,
+    pub pause_time: Option<i64>,
+    pub total_paused_duration: i64,
+    pub pause_resume_count: u8,
+    pub last_operation_slot: u64,
+    pub accumulated_dust: u64,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub acceleration_type: AccelerationType,
+    pub bump: u8,
+    pub is_funded: bool,
+}
+impl StreamV1 {
+    pub const LEN: usize = 8 +
+        32 +
+        32 +
+        8 +
+        8 +
+        8 +
+        8 +
+        8 +
+        1 +
+        1 + 8 +
+        8 +
+        1 +
+        8 +
+        8 +
+        32 +
+        32 +
+        1 +
+        1;
+    // Fields introduced after v1 take the values a fresh self-custodied,
+    // single-recipient stream without fees would have.
+    pub fn upgrade(self) -> StreamV2 {
+        StreamV2 {
+            version: 2,
+            employer: self.employer,
+            freelancer: self.freelancer,
+            stream_id: self.stream_id,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            total_amount: self.total_amount,
+            withdrawn_amount: self.withdrawn_amount,
+            is_paused: self.is_paused,
+            pause_time: self.pause_time,
+            total_paused_duration: self.total_paused_duration,
+            pause_resume_count: self.pause_resume_count,
+            last_operation_slot: self.last_operation_slot,
+            accumulated_dust: self.accumulated_dust,
+            mint: self.mint,
+            vault: self.vault,
+            acceleration_type: self.acceleration_type,
+            bump: self.bump,
+            is_funded: self.is_funded,
+            recipients: vec![StreamRecipientV2 {
+                recipient: self.freelancer,
+                share_bps: BASIS_POINTS_DENOMINATOR,
+                withdrawn_amount: self.withdrawn_amount,
+                carried_over: 0,
+            }],
+            multisig: None,
+            manager: None,
+            fee_basis_points: 0,
+            fee_mode: FeeMode::OnWithdrawal,
+            fee_recipient: Pubkey::default(),
+            fees_paid: 0,
+            receivable_mint: None,
+            renewal: None,
+            carried_over_amount: 0,
+            escrow: None,
+        }
+    }
+}
+// Borsh layout used by version 2, before streams were read in place.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StreamV2 {
+    pub version: u8,
+    pub employer: Pubkey,
+    pub freelancer: Pubkey,
+    pub stream_id: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub total_amount: u64,
+    pub withdrawn_amount: u64,
+    pub is_paused: bool,
+    pub pause_time: Option<i64>,
+    pub total_paused_duration: i64,
+    pub pause_resume_count: u8,
+    pub last_operation_slot: u64,
+    pub accumulated_dust: u64,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub acceleration_type: AccelerationType,
+    pub bump: u8,
+    pub is_funded: bool,
+    pub recipients: Vec<StreamRecipientV2>,
+    pub multisig: Option<Pubkey>,
+    pub manager: Option<Pubkey>,
+    pub fee_basis_points: u16,
+    pub fee_mode: FeeMode,
+    pub fee_recipient: Pubkey,
+    pub fees_paid: u64,
+    pub receivable_mint: Option<Pubkey>,
+    pub renewal: Option<StreamRenewalV2>,
+    pub carried_over_amount: u64,
+    pub escrow: Option<Pubkey>,
+}
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StreamRecipientV2 {
+    pub recipient: Pubkey,
+    pub share_bps: u16,
+    pub withdrawn_amount: u64,
+    pub carried_over: u64,
+}
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StreamRenewalV2 {
+    pub max_periods: u32,
+    pub periods_completed: u32,
+    pub employer_opted_out: bool,
+    pub recipient_opted_out: bool,
+}
+impl StreamV2 {
+    pub fn upgrade(self) -> Result<Stream> {
+        let mut stream = Stream::zeroed();
+        stream.version = STREAM_VERSION;
+        stream.bump = self.bump;
+        stream.is_paused = self.is_paused as u8;
+        stream.set_funded(self.is_funded);
+        stream.pause_resume_count = self.pause_resume_count;
+        stream.set_acceleration_type(self.acceleration_type);
+        stream.set_fee_mode(self.fee_mode);
+        stream.fee_basis_points = self.fee_basis_points;
+        stream.employer = self.employer;
+        stream.freelancer = self.freelancer;
+        stream.mint = self.mint;
+        stream.vault = self.vault;
+        stream.fee_recipient = self.fee_recipient;
+        stream.set_multisig(self.multisig);
+        stream.set_manager(self.manager);
+        stream.set_receivable_mint(self.receivable_mint);
+        stream.set_escrow(self.escrow);
+        stream.stream_id = self.stream_id;
+        stream.start_time = self.start_time;
+        stream.end_time = self.end_time;
+        stream.total_amount = self.total_amount;
+        stream.withdrawn_amount = self.withdrawn_amount;
+        stream.pause_time = self.pause_time.unwrap_or_default();
+        stream.total_paused_duration = self.total_paused_duration;
+        stream.last_operation_slot = self.last_operation_slot;
+        stream.accumulated_dust = self.accumulated_dust;
+        stream.fees_paid = self.fees_paid;
+        stream.carried_over_amount = self.carried_over_amount;
+        let recipients: Vec<StreamRecipient> = self.recipients
+            .iter()
+            .map(|r| StreamRecipient {
+                withdrawn_amount: r.withdrawn_amount,
+                carried_over: r.carried_over,
+                ..StreamRecipient::new(r.recipient, r.share_bps)
+            })
+            .collect();
+        stream.set_recipients(&recipients)?;
+        stream.set_renewal(self.renewal.map(|r| {
+            let mut renewal = StreamRenewal::new(r.max_periods);
+            renewal.periods_completed = r.periods_completed;
+            renewal.set_employer_opted_out(r.employer_opted_out);
+            renewal.set_recipient_opted_out(r.recipient_opted_out);
+            renewal
+        }));
+        Ok(stream)
+    }
+}
+#[zero_copy]
+#[derive(Default, Debug, PartialEq)]
+pub struct StreamRecipient {
+    pub recipient: Pubkey,
+    pub withdrawn_amount: u64,
+    pub carried_over: u64,
+    pub share_bps: u16,
+    pub _padding: [u8; 6],
+}
+impl StreamRecipient {
+    pub fn new(recipient: Pubkey, share_bps: u16) -> Self {
+        Self {
+            recipient,
+            share_bps,
+            ..Self::default()
+        }
+    }
+    pub fn share_of(&self, amount: u64) -> Result<u64> {
+        let share = (amount as u128)
+            .checked_mul(self.share_bps as u128)
+            .ok_or(error!(StreamVaultError::MathOverflow))?
+            .checked_div(BASIS_POINTS_DENOMINATOR as u128)
+            .ok_or(error!(StreamVaultError::MathOverflow))?;
+        Ok(share as u64)
+    }
+}
+#[zero_copy]
+#[derive(Default, Debug, PartialEq)]
+pub struct StreamRenewal {
+    pub max_periods: u32,
+    pub periods_completed: u32,
+    pub employer_opted_out: u8,
+    pub recipient_opted_out: u8,
+    pub _padding: [u8; 6],
+}
+impl StreamRenewal {
+    pub fn new(max_periods: u32) -> Self {
+        Self {
+            max_periods,
+            ..Self::default()
+        }
+    }
+    pub fn employer_opted_out(&self) -> bool {
+        self.employer_opted_out != 0
+    }
+    pub fn set_employer_opted_out(&mut self, opted_out: bool) {
+        self.employer_opted_out = opted_out as u8;
+    }
+    pub fn recipient_opted_out(&self) -> bool {
+        self.recipient_opted_out != 0
+    }
+    pub fn set_recipient_opted_out(&mut self, opted_out: bool) {
+        self.recipient_opted_out = opted_out as u8;
+    }
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct RecipientShare {
+    pub recipient: Pubkey,
+    pub share_bps: u16,
+}
+pub fn build_recipients(
+    freelancer: Pubkey,
+    shares: Option<Vec<RecipientShare>>,
+) -> Result<Vec<StreamRecipient>> {
+    let shares = shares.unwrap_or_else(|| vec![RecipientShare {
+        recipient: freelancer,
+        share_bps: BASIS_POINTS_DENOMINATOR,
+    }]);
+    require!(
+        !shares.is_empty() && shares.len() <= MAX_STREAM_RECIPIENTS,
+        StreamVaultError::InvalidRecipientCount
+    );
+    let mut total_bps: u32 = 0;
+    for (i, share) in shares.iter().enumerate() {
+        require!(share.share_bps > 0, StreamVaultError::InvalidRecipientShares);
+        require!(
+            !shares[..i].iter().any(|s| s.recipient == share.recipient),
+            StreamVaultError::DuplicateRecipient
+        );
+        total_bps += share.share_bps as u32;
+    }
+    require!(
+        total_bps == BASIS_POINTS_DENOMINATOR as u32,
+        StreamVaultError::InvalidRecipientShares
+    );
+    Ok(shares
+        .into_iter()
+        .map(|s| StreamRecipient::new(s.recipient, s.share_bps))
+        .collect())
+}
+#[account]
+pub struct StreamConfig {
+    pub authority: Pubkey,
+    pub fee_basis_points: u16,
+    pub fee_recipient: Pubkey,
+    pub fee_mode: FeeMode,
+    pub fee_exempt: Vec<Pubkey>,
+    pub bump: u8,
+    pub start_time_policy: StartTimePolicy,
+    pub max_slot_duration_ms: u32,
+}
+impl StreamConfig {
+    pub const LEN: usize = 8 + 
+        32 + 
+        2 + 
+        32 + 
+        1 + 
+        4 + MAX_FEE_EXEMPT_ACCOUNTS * 32 + 
+        1 +
+        1 + 8 +
+        4;
+    pub fn is_exempt(&self, key: &Pubkey) -> bool {
+        self.fee_exempt.contains(key)
+    }
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum FeeMode {
+    OnCreation,
+    OnWithdrawal,
+}
+// How far in the past a new stream may start. `Backdate` accepts a past start
+// but accrual only begins once the stream is funded; `BackdateWithCatchUp`
+// keeps the original schedule, so everything accrued since the start is
+// withdrawable as a lump sum as soon as the stream is funded. Escrowed
+// streams cannot move their schedule, so they treat `Backdate` as `Strict`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum StartTimePolicy {
+    Strict,
+    Backdate { max_backdate: i64 },
+    BackdateWithCatchUp { max_backdate: i64 },
+}
+impl StartTimePolicy {
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            StartTimePolicy::Strict => Ok(()),
+            StartTimePolicy::Backdate { max_backdate } | StartTimePolicy::BackdateWithCatchUp { max_backdate } => {
+                require!(*max_backdate >= 0, StreamVaultError::InvalidStartTimePolicy);
+                Ok(())
+            },
+        }
+    }
+    pub fn for_escrow(self) -> Self {
+        match self {
+            StartTimePolicy::Backdate { .. } => StartTimePolicy::Strict,
+            policy => policy,
+        }
+    }
+    pub fn validate_start(&self, start_time: i64, current_time: i64) -> Result<()> {
+        let backdate = current_time.saturating_sub(start_time);
+        match self {
+            StartTimePolicy::Strict => {
+                require!(backdate <= 0, StreamVaultError::StartTimeInPast);
+            },
+            StartTimePolicy::Backdate { max_backdate } | StartTimePolicy::BackdateWithCatchUp { max_backdate } => {
+                require!(backdate <= *max_backdate, StreamVaultError::StartTimeInPast);
+            },
+        }
+        Ok(())
+    }
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct WithdrawalQuote {
+    pub gross_amount: u64,
+    pub fee_amount: u64,
+    pub net_amount: u64,
+}
+#[account]
+pub struct StreamMultisig {
+    pub stream: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub proposal_count: u64,
+    pub bump: u8,
+}
+impl StreamMultisig {
+    pub const LEN: usize = 8 + 
+        32 + 
+        4 + MAX_MULTISIG_SIGNERS * 32 + 
+        1 + 
+        8 + 
+        1;
+    pub fn is_signer(&self, key: &Pubkey) -> bool {
+        self.signers.contains(key)
+    }
+    pub fn validate(signers: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            !signers.is_empty() && signers.len() <= MAX_MULTISIG_SIGNERS,
+            StreamVaultError::InvalidMultisigConfig
+        );
+        require!(
+            threshold > 0 && (threshold as usize) <= signers.len(),
+            StreamVaultError::InvalidMultisigConfig
+        );
+        for (i, signer) in signers.iter().enumerate() {
+            require!(
+                !signers[..i].contains(signer),
+                StreamVaultError::InvalidMultisigConfig
+            );
+        }
+        Ok(())
+    }
+    pub fn apply(&mut self, action: StreamAction) -> Result<()> {
+        let mut signers = self.signers.clone();
+        let mut threshold = self.threshold;
+        match action {
+            StreamAction::AddSigner { signer } => signers.push(signer),
+            StreamAction::RemoveSigner { signer } => {
+                require!(self.is_signer(&signer), StreamVaultError::InvalidMultisigConfig);
+                signers.retain(|key| *key != signer);
+            },
+            StreamAction::ChangeThreshold { threshold: new_threshold } => threshold = new_threshold,
+            _ => return Err(error!(StreamVaultError::InvalidMultisigConfig)),
+        }
+        Self::validate(&signers, threshold)?;
+        self.signers = signers;
+        self.threshold = threshold;
+        Ok(())
+    }
+}
+#[account]
+pub struct StreamProposal {
+    pub stream: Pubkey,
+    pub multisig: Pubkey,
+    pub proposal_id: u64,
+    pub action: StreamAction,
+    pub proposer: Pubkey,
+    pub approvals: Vec<Pubkey>,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub executed: bool,
+    pub bump: u8,
+}
+impl StreamProposal {
+    pub const LEN: usize = 8 + 
+        32 + 
+        32 + 
+        8 + 
+        1 + 33 + 
+        32 + 
+        4 + MAX_MULTISIG_SIGNERS * 32 + 
+        8 + 
+        8 + 
+        1 + 
+        1;
+    pub fn approve(&mut self, signer: Pubkey) -> Result<()> {
+        require!(
+            !self.approvals.contains(&signer),
+            StreamVaultError::AlreadyApproved
+        );
+        self.approvals.push(signer);
+        Ok(())
+    }
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        current_time > self.expires_at
+    }
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum StreamAction {
+    Pause,
+    Resume,
+    Close,
+    Cancel,
+    SetManager { manager: Option<Pubkey> },
+    AddSigner { signer: Pubkey },
+    RemoveSigner { signer: Pubkey },
+    ChangeThreshold { threshold: u8 },
+}
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
 pub enum StreamDuration {
     OneWeek,        
@@ -195,4 +919,364 @@ pub enum StreamStatus {
     Paused,
     Completed,
     Cancelled,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn share(recipient: Pubkey, share_bps: u16) -> RecipientShare {
+        RecipientShare { recipient, share_bps }
+    }
+    fn test_stream(total_amount: u64) -> Stream {
+        let freelancer = Pubkey::new_unique();
+        let mut stream = Stream::zeroed();
+        stream.version = STREAM_VERSION;
+        stream.employer = Pubkey::new_unique();
+        stream.freelancer = freelancer;
+        stream.stream_id = 1;
+        stream.end_time = 100;
+        stream.total_amount = total_amount;
+        stream.mint = Pubkey::new_unique();
+        stream.vault = Pubkey::new_unique();
+        stream.set_acceleration_type(AccelerationType::Linear);
+        stream.bump = 255;
+        stream.set_funded(true);
+        stream.set_recipients(&build_recipients(freelancer, None).unwrap()).unwrap();
+        stream.set_fee_mode(FeeMode::OnWithdrawal);
+        stream.fee_recipient = Pubkey::new_unique();
+        stream
+    }
+    fn stream_bytes(stream: &Stream) -> Vec<u8> {
+        let mut data = Stream::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(stream));
+        data
+    }
+    #[test]
+    fn test_default_recipient_is_freelancer() {
+        let freelancer = Pubkey::new_unique();
+        let recipients = build_recipients(freelancer, None).unwrap();
+        assert_eq!(recipients.len(), 1);
+        assert_eq!(recipients[0].recipient, freelancer);
+        assert_eq!(recipients[0].share_bps, BASIS_POINTS_DENOMINATOR);
+    }
+    #[test]
+    fn test_split_shares_must_sum_to_whole() {
+        let lead = Pubkey::new_unique();
+        let dev_a = Pubkey::new_unique();
+        let dev_b = Pubkey::new_unique();
+        let recipients = build_recipients(
+            lead,
+            Some(vec![share(lead, 5000), share(dev_a, 2500), share(dev_b, 2500)]),
+        ).unwrap();
+        assert_eq!(recipients[0].share_of(1_000_000).unwrap(), 500_000);
+        assert_eq!(recipients[1].share_of(1_000_000).unwrap(), 250_000);
+        assert!(build_recipients(lead, Some(vec![share(lead, 5000), share(dev_a, 2500)])).is_err());
+        assert!(build_recipients(lead, Some(vec![share(lead, 5000), share(lead, 5000)])).is_err());
+        assert!(build_recipients(lead, Some(vec![share(lead, 10_000), share(dev_a, 0)])).is_err());
+        assert!(build_recipients(lead, Some(vec![])).is_err());
+    }
+    #[test]
+    fn test_proposal_approvals_and_expiry() {
+        let proposer = Pubkey::new_unique();
+        let mut proposal = StreamProposal {
+            stream: Pubkey::new_unique(),
+            multisig: Pubkey::new_unique(),
+            proposal_id: 0,
+            action: StreamAction::Pause,
+            proposer,
+            approvals: vec![proposer],
+            created_at: 100,
+            expires_at: 200,
+            executed: false,
+            bump: 255,
+        };
+        assert!(proposal.approve(proposer).is_err());
+        proposal.approve(Pubkey::new_unique()).unwrap();
+        assert_eq!(proposal.approvals.len(), 2);
+        assert!(!proposal.is_expired(200));
+        assert!(proposal.is_expired(201));
+    }
+    #[test]
+    fn test_multisig_signer_rotation() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut multisig = StreamMultisig {
+            stream: Pubkey::new_unique(),
+            signers: vec![a, b],
+            threshold: 2,
+            proposal_count: 0,
+            bump: 255,
+        };
+        assert!(multisig.apply(StreamAction::RemoveSigner { signer: b }).is_err());
+        assert!(multisig.apply(StreamAction::AddSigner { signer: a }).is_err());
+        multisig.apply(StreamAction::AddSigner { signer: c }).unwrap();
+        multisig.apply(StreamAction::RemoveSigner { signer: b }).unwrap();
+        assert_eq!((multisig.signers.clone(), multisig.threshold), (vec![a, c], 2));
+        assert!(multisig.apply(StreamAction::ChangeThreshold { threshold: 3 }).is_err());
+        multisig.apply(StreamAction::ChangeThreshold { threshold: 1 }).unwrap();
+        assert!(multisig.apply(StreamAction::Pause).is_err());
+    }
+    #[test]
+    fn test_manager_acts_directly_under_multisig() {
+        let mut stream = test_stream(1_000);
+        let manager = Pubkey::new_unique();
+        stream.update_manager(Some(manager)).unwrap();
+        assert!(stream.update_manager(Some(stream.employer)).is_err());
+        assert!(!stream.needs_proposal(&stream.employer));
+        stream.set_multisig(Some(Pubkey::new_unique()));
+        assert!(stream.needs_proposal(&stream.employer));
+        assert!(!stream.needs_proposal(&manager));
+        stream.update_manager(None).unwrap();
+        assert!(stream.needs_proposal(&manager));
+    }
+    #[test]
+    fn test_fee_applies_only_in_configured_mode() {
+        let mut stream = test_stream(1_000_000);
+        stream.fee_basis_points = 250;
+        assert_eq!(stream.calculate_fee(1_000_000, FeeMode::OnWithdrawal).unwrap(), 25_000);
+        assert_eq!(stream.calculate_fee(1_000_000, FeeMode::OnCreation).unwrap(), 0);
+        stream.set_fee_mode(FeeMode::OnCreation);
+        assert_eq!(stream.calculate_fee(1_000_000, FeeMode::OnCreation).unwrap(), 25_000);
+        assert_eq!(stream.calculate_fee(1_000_000, FeeMode::OnWithdrawal).unwrap(), 0);
+    }
+    #[test]
+    fn test_recipient_earnings_follow_shares() {
+        let mut stream = test_stream(1_000);
+        let dev = Pubkey::new_unique();
+        stream.set_recipients(&build_recipients(
+            stream.freelancer,
+            Some(vec![share(stream.freelancer, 5000), share(dev, 5000)]),
+        ).unwrap()).unwrap();
+        assert_eq!(stream.calculate_recipient_earned_amount(0, 50).unwrap(), 250);
+        stream.record_recipient_withdrawal(1, 100).unwrap();
+        assert_eq!(stream.calculate_recipient_withdrawable_amount(1, 50).unwrap(), 150);
+        assert_eq!(stream.withdrawn_amount, 100);
+    }
+    fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> TokenAccount {
+        use anchor_lang::solana_program::program_pack::Pack;
+        use anchor_spl::token::spl_token;
+        let mut data = [0u8; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }.pack_into_slice(&mut data);
+        TokenAccount::try_deserialize_unchecked(&mut &data[..]).unwrap()
+    }
+    #[test]
+    fn test_receivable_holder_replaces_recipient_check() {
+        let mut stream = test_stream(1_000);
+        let freelancer = stream.freelancer;
+        let buyer = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let holding = token_account(mint, buyer, 1);
+        assert_eq!(stream.authorize_withdrawal(&freelancer, None).unwrap(), 0);
+        assert!(stream.authorize_withdrawal(&buyer, Some(&holding)).is_err());
+        stream.set_receivable_mint(Some(mint));
+        assert_eq!(stream.authorize_withdrawal(&buyer, Some(&holding)).unwrap(), 0);
+        assert!(stream.authorize_withdrawal(&freelancer, None).is_err());
+        let sold = token_account(mint, buyer, 0);
+        assert!(stream.authorize_withdrawal(&buyer, Some(&sold)).is_err());
+    }
+    #[test]
+    fn test_roll_period_carries_over_unwithdrawn_earnings() {
+        let mut stream = test_stream(1_000);
+        stream.set_renewal(Some(StreamRenewal::new(2)));
+        stream.total_paused_duration = 10;
+        stream.record_recipient_withdrawal(0, 400).unwrap();
+        assert!(stream.can_renew());
+        stream.roll_period().unwrap();
+        assert_eq!(stream.start_time, 110);
+        assert_eq!(stream.end_time, 210);
+        assert_eq!(stream.carried_over_amount, 600);
+        assert_eq!(stream.withdrawn_amount, 0);
+        assert_eq!(stream.calculate_recipient_withdrawable_amount(0, 160).unwrap(), 1_100);
+        stream.roll_period().unwrap();
+        assert!(!stream.can_renew());
+    }
+    fn v1_stream_bytes(legacy: &StreamV1) -> Vec<u8> {
+        let mut data = Stream::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+        data.resize(StreamV1::LEN, 0);
+        data
+    }
+    #[test]
+    fn test_len_matches_fixed_layout() {
+        assert_eq!(std::mem::size_of::<StreamRecipient>(), 56);
+        assert_eq!(std::mem::size_of::<StreamRenewal>(), 16);
+        assert_eq!(Stream::LEN, 8 + 688);
+        let mut stream = test_stream(1_000_000);
+        stream.set_recipients(&[stream.recipients()[0]; MAX_STREAM_RECIPIENTS]).unwrap();
+        assert!(stream.set_recipients(&[stream.recipients()[0]; MAX_STREAM_RECIPIENTS + 1]).is_err());
+        assert_eq!(stream_bytes(&stream).len(), Stream::LEN);
+    }
+    #[test]
+    fn test_migrates_v1_stream_bytes() {
+        let freelancer = Pubkey::new_unique();
+        let legacy = StreamV1 {
+            employer: Pubkey::new_unique(),
+            freelancer,
+            stream_id: 7,
+            start_time: 1_000,
+            end_time: 2_000,
+            total_amount: 500_000,
+            withdrawn_amount: 125_000,
+            is_paused: false,
+            pause_time: None,
+            total_paused_duration: 30,
+            pause_resume_count: 1,
+            last_operation_slot: 42,
+            accumulated_dust: 3,
+            mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            acceleration_type: AccelerationType::Quadratic,
+            bump: 254,
+            is_funded: true,
+        };
+        let data = v1_stream_bytes(&legacy);
+        let (stream, from_version) = Stream::from_versioned_bytes(&data).unwrap();
+        assert_eq!(from_version, 1);
+        assert_eq!(stream.version, STREAM_VERSION);
+        assert_eq!(stream.employer, legacy.employer);
+        assert_eq!(stream.stream_id, 7);
+        assert_eq!(stream.withdrawn_amount, 125_000);
+        assert_eq!(stream.total_paused_duration, 30);
+        assert_eq!(stream.acceleration_type(), AccelerationType::Quadratic);
+        assert_eq!(stream.recipients().len(), 1);
+        assert_eq!(stream.recipients()[0].recipient, freelancer);
+        assert_eq!(stream.recipients()[0].withdrawn_amount, 125_000);
+        assert_eq!(stream.fee_basis_points, 0);
+        assert_eq!(stream.escrow(), None);
+        assert_eq!(
+            stream.calculate_recipient_withdrawable_amount(0, 2_030).unwrap(),
+            375_000
+        );
+
+        let (stream, from_version) = Stream::from_versioned_bytes(&stream_bytes(&stream)).unwrap();
+        assert_eq!(from_version, STREAM_VERSION);
+        assert_eq!(stream.stream_id, 7);
+    }
+    #[test]
+    fn test_detects_v1_stream_from_original_layout() {
+        // Written field by field the way the original `Stream` account was,
+        // into its 213-byte allocation. The employer key starts with a byte
+        // that would also read as version 2.
+        let employer = Pubkey::new_from_array([2; 32]);
+        let (freelancer, mint, vault) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = Stream::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(employer.as_ref());
+        data.extend_from_slice(freelancer.as_ref());
+        data.extend_from_slice(&7u64.to_le_bytes());
+        data.extend_from_slice(&1_000i64.to_le_bytes());
+        data.extend_from_slice(&2_000i64.to_le_bytes());
+        data.extend_from_slice(&500_000u64.to_le_bytes());
+        data.extend_from_slice(&125_000u64.to_le_bytes());
+        data.extend_from_slice(&[0, 0]);
+        data.extend_from_slice(&30i64.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data.extend_from_slice(&3u64.to_le_bytes());
+        data.extend_from_slice(mint.as_ref());
+        data.extend_from_slice(vault.as_ref());
+        data.extend_from_slice(&[1, 254, 1]);
+        data.resize(213, 0);
+        assert_eq!(StreamV1::LEN, 213);
+        let (stream, from_version) = Stream::from_versioned_bytes(&data).unwrap();
+        assert_eq!(from_version, 1);
+        assert_eq!((stream.employer, stream.mint, stream.vault), (employer, mint, vault));
+        assert_eq!((stream.start_time, stream.end_time, stream.total_paused_duration), (1_000, 2_000, 30));
+        assert_eq!(stream.acceleration_type(), AccelerationType::Quadratic);
+        assert_eq!((stream.bump, stream.is_funded()), (254, true));
+        assert_eq!(stream.recipients()[0].recipient, freelancer);
+    }
+    #[test]
+    fn test_migrates_v2_stream_bytes() {
+        let freelancer = Pubkey::new_unique();
+        let dev = Pubkey::new_unique();
+        let escrow = Pubkey::new_unique();
+        let legacy = StreamV2 {
+            version: 2,
+            employer: Pubkey::new_unique(),
+            freelancer,
+            stream_id: 9,
+            start_time: 0,
+            end_time: 100,
+            total_amount: 1_000,
+            withdrawn_amount: 200,
+            is_paused: true,
+            pause_time: Some(40),
+            total_paused_duration: 5,
+            pause_resume_count: 3,
+            last_operation_slot: 11,
+            accumulated_dust: 0,
+            mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            acceleration_type: AccelerationType::Cubic,
+            bump: 253,
+            is_funded: true,
+            recipients: vec![
+                StreamRecipientV2 { recipient: freelancer, share_bps: 6000, withdrawn_amount: 200, carried_over: 0 },
+                StreamRecipientV2 { recipient: dev, share_bps: 4000, withdrawn_amount: 0, carried_over: 50 },
+            ],
+            multisig: None,
+            manager: Some(Pubkey::new_unique()),
+            fee_basis_points: 100,
+            fee_mode: FeeMode::OnCreation,
+            fee_recipient: Pubkey::new_unique(),
+            fees_paid: 10,
+            receivable_mint: None,
+            renewal: Some(StreamRenewalV2 {
+                max_periods: 4,
+                periods_completed: 1,
+                employer_opted_out: false,
+                recipient_opted_out: true,
+            }),
+            carried_over_amount: 50,
+            escrow: Some(escrow),
+        };
+        let mut data = Stream::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+        let (stream, from_version) = Stream::from_versioned_bytes(&data).unwrap();
+        assert_eq!(from_version, 2);
+        assert_eq!(stream.version, STREAM_VERSION);
+        assert!(stream.is_paused());
+        assert_eq!(stream.pause_time(), Some(40));
+        assert_eq!(stream.acceleration_type(), AccelerationType::Cubic);
+        assert_eq!(stream.fee_mode(), FeeMode::OnCreation);
+        assert_eq!(stream.multisig(), None);
+        assert_eq!(stream.manager(), legacy.manager);
+        assert_eq!(stream.escrow(), Some(escrow));
+        assert_eq!(stream.recipients().len(), 2);
+        assert_eq!(stream.recipients()[1].recipient, dev);
+        assert_eq!(stream.recipients()[1].carried_over, 50);
+        let renewal = stream.renewal().unwrap();
+        assert_eq!(renewal.periods_completed, 1);
+        assert!(!renewal.employer_opted_out());
+        assert!(renewal.recipient_opted_out());
+    }
+    #[test]
+    fn test_rejects_unknown_stream_version() {
+        let mut stream = test_stream(1_000);
+        stream.version = STREAM_VERSION + 1;
+        let data = stream_bytes(&stream);
+        assert!(Stream::from_versioned_bytes(&data).is_err());
+        assert!(Stream::from_versioned_bytes(&data[..StreamV1::LEN - 1]).is_err());
+    }
+    #[test]
+    fn test_start_time_policy_bounds_backdating() {
+        assert!(StartTimePolicy::Strict.validate_start(100, 100).is_ok());
+        assert!(StartTimePolicy::Strict.validate_start(99, 100).is_err());
+        let backdate = StartTimePolicy::Backdate { max_backdate: 50 };
+        assert!(backdate.validate_start(50, 100).is_ok());
+        assert!(backdate.validate_start(49, 100).is_err());
+        assert!(StartTimePolicy::BackdateWithCatchUp { max_backdate: -1 }.validate().is_err());
+        let mut stream = test_stream(1_000);
+        stream.set_start_time_policy(backdate);
+        assert!(stream.allows_late_funding() && !stream.catches_up());
+        stream.set_start_time_policy(StartTimePolicy::BackdateWithCatchUp { max_backdate: 50 });
+        assert!(stream.catches_up());
+        assert_eq!(backdate.for_escrow(), StartTimePolicy::Strict);
+        let catch_up = StartTimePolicy::BackdateWithCatchUp { max_backdate: 50 };
+        assert_eq!(catch_up.for_escrow(), catch_up);
+    }
 }
\ No newline at end of file
@@ -55,7 +55,8 @@ impl StreamingStrategy for ExponentialStreamingStrategy {
             .checked_sub(effective_start)
             .ok_or(error!(StreamVaultError::MathOverflow))?
             .checked_sub(total_paused_duration)
-            .ok_or(error!(StreamVaultError::MathOverflow))?;
+            .ok_or(error!(StreamVaultError::MathOverflow))?
+            .max(0);
         let total_duration = end_time
             .checked_sub(start_time)
             .ok_or(error!(StreamVaultError::MathOverflow))?;
@@ -181,6 +182,16 @@ mod tests {
         assert_eq!(earned, 250);
     }
     #[test]
+    fn test_pause_before_start_earns_nothing() {
+        let strategy = ExponentialStreamingStrategy::new(AccelerationType::Quadratic);
+        let earned = strategy.calculate_earned_amount(1000, 100, 200, 120, 0, true, Some(80)).unwrap();
+        assert_eq!(earned, 0);
+        let earned = strategy.calculate_earned_amount(1000, 100, 200, 120, 50, false, None).unwrap();
+        assert_eq!(earned, 0);
+        let earned = strategy.calculate_earned_amount(1000, 100, 200, 200, 50, false, None).unwrap();
+        assert_eq!(earned, 250);
+    }
+    #[test]
     fn test_exponential_vs_linear() {
         let linear = LinearStreamingStrategy::new();
         let exponential = ExponentialStreamingStrategy::new(AccelerationType::Quadratic);
@@ -1,13 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, TokenAccount};
 use crate::errors::StreamVaultError;
-pub fn validate_time_range(start_time: i64, end_time: i64, current_time: i64) -> Result<()> {
+pub fn validate_time_range(start_time: i64, end_time: i64) -> Result<()> {
     if end_time <= start_time {
         return Err(error!(StreamVaultError::InvalidTimeRange));
     }
-    if start_time < current_time {
-        msg!("Warning: Sprint start time is in the past");
-    }
     Ok(())
 }
 pub fn validate_amount(amount: u64) -> Result<()> {
@@ -19,6 +16,29 @@ pub fn validate_amount(amount: u64) -> Result<()> {
 pub fn get_current_time() -> Result<i64> {
     Ok(Clock::get()?.unix_timestamp)
 }
+// Cross-checks the cluster time against the slots elapsed since the epoch
+// started: the time returned never runs ahead of `max_slot_duration_ms` per
+// slot, so a clock anomaly cannot accelerate accrual. Zero disables the guard.
+pub fn get_guarded_time(max_slot_duration_ms: u32) -> Result<i64> {
+    let clock = Clock::get()?;
+    if max_slot_duration_ms == 0 {
+        return Ok(clock.unix_timestamp);
+    }
+    let first_slot = EpochSchedule::get()?.get_first_slot_in_epoch(clock.epoch);
+    let elapsed_slots = clock.slot.saturating_sub(first_slot);
+    let max_elapsed = (elapsed_slots as u128 * max_slot_duration_ms as u128 / 1000).min(i64::MAX as u128) as i64;
+    let guarded_time = clock.epoch_start_timestamp.saturating_add(max_elapsed);
+    if clock.unix_timestamp > guarded_time {
+        msg!(
+            "Clock guard: cluster time {} exceeds {} slots since epoch start, using {}",
+            clock.unix_timestamp,
+            elapsed_slots,
+            guarded_time
+        );
+        return Ok(guarded_time);
+    }
+    Ok(clock.unix_timestamp)
+}
 pub fn calculate_release_rate(total_amount: u64, start_time: i64, end_time: i64) -> Result<u64> {
     let duration = end_time
         .checked_sub(start_time)
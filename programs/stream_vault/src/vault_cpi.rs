@@ -0,0 +1,440 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use crate::constants::VAULT_PROGRAM_ID;
+use crate::errors::StreamVaultError;
+use crate::strategies::AccelerationType;
+
+// The vault program is built against a different anchor-lang release, so its
+// instructions are assembled by hand rather than through its `cpi` feature.
+// Discriminators are `sha256("global:<instruction>")[..8]`.
+pub const CREATE_ESCROW_DISCRIMINATOR: [u8; 8] = [253, 215, 165, 116, 36, 108, 68, 80];
+pub const DEPOSIT_FUNDS_DISCRIMINATOR: [u8; 8] = [202, 39, 52, 211, 53, 20, 250, 88];
+pub const WITHDRAW_AVAILABLE_DISCRIMINATOR: [u8; 8] = [145, 100, 91, 136, 108, 149, 60, 134];
+pub const CLOSE_ESCROW_DISCRIMINATOR: [u8; 8] = [139, 171, 94, 146, 191, 91, 144, 50];
+
+// Mirrors of the vault program's argument types. Variant order must match
+// `vault::state` exactly since Borsh encodes enums by index.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum ReleaseSchedule {
+    Immediate,
+    Linear {
+        start: i64,
+        end: i64,
+    },
+    Milestone {
+        conditions: Vec<MilestoneCondition>,
+    },
+    Hybrid {
+        linear_portion: u64,
+        milestone_portion: u64,
+        linear_config: LinearConfig,
+        milestone_config: Vec<MilestoneCondition>,
+    },
+    Custom {
+        data: Vec<u8>,
+    },
+    Steps {
+        entries: Vec<StepEntry>,
+    },
+    Oracle {
+        oracle_program: Pubkey,
+        data: Vec<u8>,
+    },
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct StepEntry {
+    pub timestamp: i64,
+    pub cumulative_amount: u64,
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MilestoneCondition {
+    pub milestone_id: u32,
+    pub amount: u64,
+    pub required_approval: Pubkey,
+    pub is_completed: bool,
+    pub deadline: Option<i64>,
+    pub missed_outcome: MissedMilestoneOutcome,
+    pub is_refunded: bool,
+    pub is_submitted: bool,
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum MissedMilestoneOutcome {
+    RefundDepositor,
+    PayBeneficiary,
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LinearConfig {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub acceleration_type: VaultAccelerationType,
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum VaultAccelerationType {
+    Linear,
+    Quadratic,
+    Cubic,
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum VaultFeeMode {
+    OnTop,
+    Inclusive,
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum VaultExpiryPolicy {
+    RefundAll,
+    PayVested,
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum ReleaseAuthority {
+    Beneficiary,
+    Depositor,
+    Either,
+    Both,
+    Program(Pubkey),
+    Arbiter,
+    Threshold {
+        signers: Vec<Pubkey>,
+        m: u8,
+    },
+}
+pub fn escrow_vault_id(stream: &Pubkey) -> u64 {
+    let mut id = [0u8; 8];
+    id.copy_from_slice(&stream.to_bytes()[..8]);
+    u64::from_le_bytes(id)
+}
+pub fn release_schedule_for(
+    acceleration_type: AccelerationType,
+    start_time: i64,
+    end_time: i64,
+    total_amount: u64,
+) -> ReleaseSchedule {
+    let acceleration_type = match acceleration_type {
+        AccelerationType::Linear => {
+            return ReleaseSchedule::Linear { start: start_time, end: end_time };
+        },
+        AccelerationType::Quadratic => VaultAccelerationType::Quadratic,
+        AccelerationType::Cubic => VaultAccelerationType::Cubic,
+    };
+    ReleaseSchedule::Hybrid {
+        linear_portion: total_amount,
+        milestone_portion: 0,
+        linear_config: LinearConfig {
+            start_time,
+            end_time,
+            acceleration_type,
+        },
+        milestone_config: vec![],
+    }
+}
+pub struct CreateEscrow<'info> {
+    pub escrow_vault: AccountInfo<'info>,
+    pub vault_token_account: AccountInfo<'info>,
+    pub config: AccountInfo<'info>,
+    pub depositor: AccountInfo<'info>,
+    pub beneficiary: AccountInfo<'info>,
+    pub owner_program: AccountInfo<'info>,
+    pub owner_account: AccountInfo<'info>,
+    pub token_mint: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub associated_token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+pub fn create_escrow<'info>(
+    vault_program: &AccountInfo<'info>,
+    accounts: CreateEscrow<'info>,
+    vault_id: u64,
+    total_amount: u64,
+    release_schedule: ReleaseSchedule,
+    release_authority: ReleaseAuthority,
+) -> Result<()> {
+    let mut data = CREATE_ESCROW_DISCRIMINATOR.to_vec();
+    vault_id.serialize(&mut data)?;
+    total_amount.serialize(&mut data)?;
+    release_schedule.serialize(&mut data)?;
+    release_authority.serialize(&mut data)?;
+    None::<i64>.serialize(&mut data)?;
+    None::<Pubkey>.serialize(&mut data)?;
+    let instruction = Instruction {
+        program_id: vault_program.key(),
+        accounts: vec![
+            AccountMeta::new(accounts.escrow_vault.key(), false),
+            AccountMeta::new(accounts.vault_token_account.key(), false),
+            AccountMeta::new_readonly(accounts.config.key(), false),
+            AccountMeta::new(accounts.depositor.key(), true),
+            AccountMeta::new_readonly(accounts.beneficiary.key(), false),
+            AccountMeta::new_readonly(accounts.owner_program.key(), false),
+            AccountMeta::new_readonly(accounts.owner_account.key(), false),
+            AccountMeta::new_readonly(accounts.token_mint.key(), false),
+            AccountMeta::new_readonly(accounts.token_program.key(), false),
+            AccountMeta::new_readonly(accounts.associated_token_program.key(), false),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data,
+    };
+    invoke(
+        &instruction,
+        &[
+            accounts.escrow_vault,
+            accounts.vault_token_account,
+            accounts.config,
+            accounts.depositor,
+            accounts.beneficiary,
+            accounts.owner_program,
+            accounts.owner_account,
+            accounts.token_mint,
+            accounts.token_program,
+            accounts.associated_token_program,
+            accounts.system_program,
+            vault_program.clone(),
+        ],
+    )?;
+    Ok(())
+}
+pub struct DepositFunds<'info> {
+    pub escrow_vault: AccountInfo<'info>,
+    pub vault_token_account: AccountInfo<'info>,
+    pub depositor: AccountInfo<'info>,
+    pub depositor_token_account: AccountInfo<'info>,
+    pub config: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub fee_token_account: Option<AccountInfo<'info>>,
+    pub fee_ledger: Option<AccountInfo<'info>>,
+}
+pub fn deposit_funds<'info>(
+    vault_program: &AccountInfo<'info>,
+    accounts: DepositFunds<'info>,
+    amount: u64,
+) -> Result<()> {
+    let mut data = DEPOSIT_FUNDS_DISCRIMINATOR.to_vec();
+    amount.serialize(&mut data)?;
+    let instruction = Instruction {
+        program_id: vault_program.key(),
+        accounts: vec![
+            AccountMeta::new(accounts.escrow_vault.key(), false),
+            AccountMeta::new(accounts.vault_token_account.key(), false),
+            AccountMeta::new(accounts.depositor.key(), true),
+            AccountMeta::new(accounts.depositor_token_account.key(), false),
+            AccountMeta::new_readonly(accounts.config.key(), false),
+            AccountMeta::new_readonly(accounts.token_program.key(), false),
+            optional_account_meta(vault_program, accounts.fee_token_account.as_ref()),
+            optional_account_meta(vault_program, accounts.fee_ledger.as_ref()),
+        ],
+        data,
+    };
+    let mut account_infos = vec![
+        accounts.escrow_vault,
+        accounts.vault_token_account,
+        accounts.depositor,
+        accounts.depositor_token_account,
+        accounts.config,
+        accounts.token_program,
+        vault_program.clone(),
+    ];
+    account_infos.extend(accounts.fee_token_account);
+    account_infos.extend(accounts.fee_ledger);
+    invoke(&instruction, &account_infos)?;
+    Ok(())
+}
+// Anchor reads an optional account passed as the program id as `None`.
+fn optional_account_meta(vault_program: &AccountInfo, account: Option<&AccountInfo>) -> AccountMeta {
+    match account {
+        Some(account) => AccountMeta::new(account.key(), false),
+        None => AccountMeta::new_readonly(vault_program.key(), false),
+    }
+}
+pub struct WithdrawAvailable<'info> {
+    pub escrow_vault: AccountInfo<'info>,
+    pub vault_token_account: AccountInfo<'info>,
+    pub withdrawer: AccountInfo<'info>,
+    pub withdrawer_token_account: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+pub fn withdraw_available<'info>(
+    vault_program: &AccountInfo<'info>,
+    accounts: WithdrawAvailable<'info>,
+    max_amount: Option<u64>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = WITHDRAW_AVAILABLE_DISCRIMINATOR.to_vec();
+    max_amount.serialize(&mut data)?;
+    let instruction = Instruction {
+        program_id: vault_program.key(),
+        accounts: vec![
+            AccountMeta::new(accounts.escrow_vault.key(), false),
+            AccountMeta::new(accounts.vault_token_account.key(), false),
+            AccountMeta::new(accounts.withdrawer.key(), true),
+            AccountMeta::new(accounts.withdrawer_token_account.key(), false),
+            AccountMeta::new_readonly(accounts.token_program.key(), false),
+        ],
+        data,
+    };
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.escrow_vault,
+            accounts.vault_token_account,
+            accounts.withdrawer,
+            accounts.withdrawer_token_account,
+            accounts.token_program,
+            vault_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+pub struct CloseEscrow<'info> {
+    pub escrow_vault: AccountInfo<'info>,
+    pub vault_token_account: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub depositor: AccountInfo<'info>,
+    pub depositor_token_account: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+pub fn close_escrow<'info>(
+    vault_program: &AccountInfo<'info>,
+    accounts: CloseEscrow<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let instruction = Instruction {
+        program_id: vault_program.key(),
+        accounts: vec![
+            AccountMeta::new(accounts.escrow_vault.key(), false),
+            AccountMeta::new(accounts.vault_token_account.key(), false),
+            AccountMeta::new_readonly(accounts.authority.key(), true),
+            AccountMeta::new(accounts.depositor.key(), false),
+            AccountMeta::new(accounts.depositor_token_account.key(), false),
+            AccountMeta::new_readonly(accounts.token_program.key(), false),
+        ],
+        data: CLOSE_ESCROW_DISCRIMINATOR.to_vec(),
+    };
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.escrow_vault,
+            accounts.vault_token_account,
+            accounts.authority,
+            accounts.depositor,
+            accounts.depositor_token_account,
+            accounts.token_program,
+            vault_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+// Escrow-side accounts a stream held in vault custody passes alongside its own.
+pub struct EscrowAccounts<'info> {
+    pub escrow_vault: AccountInfo<'info>,
+    pub escrow_token_account: AccountInfo<'info>,
+    pub vault_program: AccountInfo<'info>,
+}
+impl<'info> EscrowAccounts<'info> {
+    // Returns `None` for legacy self-custodied streams and requires every escrow
+    // account otherwise.
+    pub fn load(
+        escrow: Option<Pubkey>,
+        escrow_vault: Option<&UncheckedAccount<'info>>,
+        escrow_token_account: Option<&UncheckedAccount<'info>>,
+        vault_program: Option<&UncheckedAccount<'info>>,
+    ) -> Result<Option<Self>> {
+        let Some(escrow) = escrow else {
+            return Ok(None);
+        };
+        let (Some(escrow_vault), Some(escrow_token_account), Some(vault_program)) =
+            (escrow_vault, escrow_token_account, vault_program)
+        else {
+            return Err(error!(StreamVaultError::MissingEscrowAccounts));
+        };
+        require_keys_eq!(escrow_vault.key(), escrow, StreamVaultError::InvalidEscrowAccount);
+        require_keys_eq!(vault_program.key(), VAULT_PROGRAM_ID, StreamVaultError::InvalidEscrowAccount);
+        Ok(Some(Self {
+            escrow_vault: escrow_vault.to_account_info(),
+            escrow_token_account: escrow_token_account.to_account_info(),
+            vault_program: vault_program.to_account_info(),
+        }))
+    }
+    // Releases `amount` from the escrow into the stream's own token account, which
+    // then pays out exactly as a self-custodied stream would.
+    pub fn release_to_stream(
+        &self,
+        stream: AccountInfo<'info>,
+        stream_token_account: AccountInfo<'info>,
+        token_program: AccountInfo<'info>,
+        amount: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        withdraw_available(
+            &self.vault_program,
+            WithdrawAvailable {
+                escrow_vault: self.escrow_vault.clone(),
+                vault_token_account: self.escrow_token_account.clone(),
+                withdrawer: stream,
+                withdrawer_token_account: stream_token_account,
+                token_program,
+            },
+            Some(amount),
+            signer_seeds,
+        )
+    }
+    // Closes the escrow, refunding whatever it still holds to the employer.
+    pub fn close(
+        &self,
+        stream: AccountInfo<'info>,
+        employer: AccountInfo<'info>,
+        employer_token_account: AccountInfo<'info>,
+        token_program: AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        close_escrow(
+            &self.vault_program,
+            CloseEscrow {
+                escrow_vault: self.escrow_vault.clone(),
+                vault_token_account: self.escrow_token_account.clone(),
+                authority: stream,
+                depositor: employer,
+                depositor_token_account: employer_token_account,
+                token_program,
+            },
+            signer_seeds,
+        )
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+    fn discriminator(name: &str) -> [u8; 8] {
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash(format!("global:{}", name).as_bytes()).to_bytes()[..8]);
+        discriminator
+    }
+    #[test]
+    fn test_discriminators_match_vault_instructions() {
+        assert_eq!(CREATE_ESCROW_DISCRIMINATOR, discriminator("create_escrow"));
+        assert_eq!(DEPOSIT_FUNDS_DISCRIMINATOR, discriminator("deposit_funds"));
+        assert_eq!(WITHDRAW_AVAILABLE_DISCRIMINATOR, discriminator("withdraw_available"));
+        assert_eq!(CLOSE_ESCROW_DISCRIMINATOR, discriminator("close_escrow"));
+    }
+    #[test]
+    fn test_release_schedule_follows_acceleration() {
+        let linear = release_schedule_for(AccelerationType::Linear, 100, 200, 1_000).try_to_vec().unwrap();
+        assert_eq!(linear[0], 1);
+        assert_eq!(&linear[1..9], &100i64.to_le_bytes());
+        assert_eq!(&linear[9..17], &200i64.to_le_bytes());
+        let cubic = release_schedule_for(AccelerationType::Cubic, 100, 200, 1_000).try_to_vec().unwrap();
+        assert_eq!(cubic[0], 3);
+        assert_eq!(&cubic[1..9], &1_000u64.to_le_bytes());
+        assert_eq!(&cubic[9..17], &0u64.to_le_bytes());
+        assert_eq!(cubic[33], 2);
+    }
+    #[test]
+    fn test_escrow_vault_id_is_stable_per_stream() {
+        let stream = Pubkey::new_unique();
+        assert_eq!(escrow_vault_id(&stream), escrow_vault_id(&stream));
+        assert_ne!(escrow_vault_id(&stream), escrow_vault_id(&Pubkey::new_unique()));
+        let authority = ReleaseAuthority::Program(stream).try_to_vec().unwrap();
+        assert_eq!(authority[0], 4);
+        assert_eq!(&authority[1..], stream.as_ref());
+    }
+}
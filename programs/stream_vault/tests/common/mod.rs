@@ -0,0 +1,657 @@
+// Shared in-process harness for the integration suites. `stream_vault` can run
+// natively from this crate; `vault` is built against another anchor release, so
+// anything touching it needs the SBF builds in `target/deploy` (`anchor build`),
+// as does the sample release oracle its custom schedules call.
+#![allow(dead_code)]
+pub mod vault;
+use std::path::PathBuf;
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::account_info::AccountInfo;
+use solana_sdk::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use solana_sdk::clock::Clock;
+use solana_sdk::entrypoint::ProgramResult;
+use solana_sdk::instruction::{AccountMeta, Instruction, InstructionError};
+use solana_sdk::program_option::COption;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_program;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use stream_vault::constants::VAULT_PROGRAM_ID;
+use stream_vault::state::{FeeMode, RecipientShare, StartTimePolicy, Stream, StreamAction, StreamDuration, StreamMultisig};
+use stream_vault::strategies::AccelerationType;
+use stream_vault::vault_cpi::escrow_vault_id;
+pub const DECIMALS: u8 = 6;
+pub const GENESIS_TIME: i64 = 1_700_000_000;
+pub const WEEK: i64 = 7 * 24 * 60 * 60;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Runtime {
+    Native,
+    Sbf,
+}
+// The error a transaction fails with when its first instruction returns `code`.
+pub fn program_error(code: impl Into<u32>) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(code.into()))
+}
+// Directory holding the program builds, if they have been built.
+pub fn sbf_out_dir() -> Option<PathBuf> {
+    let dir = std::env::var("SBF_OUT_DIR")
+        .or_else(|_| std::env::var("BPF_OUT_DIR"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy"));
+    let built = ["stream_vault.so", "vault.so", "cliff_release_oracle.so"].iter().all(|name| dir.join(name).is_file());
+    built.then_some(dir)
+}
+fn process_stream_vault(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // Anchor's entrypoint wants the account slice and infos to share a lifetime.
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    stream_vault::entry(program_id, accounts, data)
+}
+pub struct Harness {
+    pub context: ProgramTestContext,
+    pub runtime: Runtime,
+    pub mint: Pubkey,
+    pub fee_recipient: Pubkey,
+}
+impl Harness {
+    // Starts the runtime with both configs initialized and no fees.
+    pub async fn start(runtime: Runtime) -> Self {
+        let mut harness = Self::new(runtime).await;
+        let payer = harness.payer();
+        harness.process(
+            &[initialize_stream_config(&payer.pubkey(), &harness.fee_recipient, 0, FeeMode::OnWithdrawal)],
+            &[],
+        ).await.unwrap();
+        if runtime == Runtime::Sbf {
+            harness.initialize_vault_config().await;
+        }
+        harness
+    }
+    pub async fn initialize_vault_config(&mut self) -> u64 {
+        let payer = self.payer();
+        let ix = vault::initialize_config(&payer.pubkey(), &self.fee_recipient, vault::ConfigParams::default());
+        self.process(&[ix], &[]).await.unwrap()
+    }
+    pub async fn new(runtime: Runtime) -> Self {
+        let mut program_test = ProgramTest::default();
+        match runtime {
+            Runtime::Native => {
+                program_test.prefer_bpf(false);
+                program_test.add_program("stream_vault", stream_vault::ID, processor!(process_stream_vault));
+            },
+            Runtime::Sbf => {
+                let dir = sbf_out_dir().expect("SBF builds missing; run `anchor build`");
+                std::env::set_var("SBF_OUT_DIR", dir);
+                program_test.prefer_bpf(true);
+                program_test.add_program("stream_vault", stream_vault::ID, None);
+                program_test.add_program("vault", VAULT_PROGRAM_ID, None);
+                program_test.add_program("cliff_release_oracle", vault::CLIFF_RELEASE_ORACLE_ID, None);
+            },
+        }
+        let context = program_test.start_with_context().await;
+        let mut harness = Self {
+            context,
+            runtime,
+            mint: Pubkey::new_unique(),
+            fee_recipient: Pubkey::new_unique(),
+        };
+        harness.set_mint(harness.mint, DECIMALS);
+        let payer = harness.payer().pubkey();
+        harness.set_upgrade_authority(Some(payer));
+        harness.set_clock(GENESIS_TIME).await;
+        harness
+    }
+    // The programs are loaded without an upgradeable deployment, so the
+    // program data account that names the upgrade authority is set by hand.
+    pub fn set_upgrade_authority(&mut self, upgrade_authority_address: Option<Pubkey>) {
+        let state = UpgradeableLoaderState::ProgramData { slot: 0, upgrade_authority_address };
+        let size = UpgradeableLoaderState::size_of_programdata_metadata();
+        let account = AccountSharedData::new_data_with_space(
+            Rent::default().minimum_balance(size),
+            &state,
+            size,
+            &bpf_loader_upgradeable::ID,
+        ).unwrap();
+        self.context.set_account(&program_data_address(), &account);
+    }
+    pub fn payer(&self) -> Keypair {
+        self.context.payer.insecure_clone()
+    }
+    // Sends the instructions in one transaction paid by the test payer and
+    // returns the compute units it consumed.
+    pub async fn process(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<u64, TransactionError> {
+        let payer = self.payer();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        let blockhash = self.context.banks_client.get_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &all_signers, blockhash);
+        let outcome = self.context.banks_client
+            .process_transaction_with_metadata(transaction)
+            .await
+            .unwrap();
+        outcome.result?;
+        Ok(outcome.metadata.map(|metadata| metadata.compute_units_consumed).unwrap_or_default())
+    }
+    pub async fn simulate(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> (Result<(), TransactionError>, Option<Vec<u8>>) {
+        let payer = self.payer();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        let blockhash = self.context.banks_client.get_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &all_signers, blockhash);
+        let outcome = self.context.banks_client.simulate_transaction(transaction).await.unwrap();
+        let return_data = outcome.simulation_details
+            .and_then(|details| details.return_data)
+            .map(|return_data| return_data.data);
+        (outcome.result.unwrap_or(Ok(())), return_data)
+    }
+    pub async fn now(&mut self) -> i64 {
+        self.context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp
+    }
+    // Moves to the next slot and sets the cluster time, so that consecutive
+    // operations never share a slot or a blockhash.
+    pub async fn set_clock(&mut self, unix_timestamp: i64) {
+        self.update_clock(|clock| clock.unix_timestamp = unix_timestamp).await;
+    }
+    pub async fn update_clock(&mut self, update: impl FnOnce(&mut Clock)) {
+        let slot = self.context.banks_client.get_sysvar::<Clock>().await.unwrap().slot;
+        self.context.warp_to_slot(slot + 1).unwrap();
+        let mut clock = self.context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        update(&mut clock);
+        self.context.set_sysvar(&clock);
+    }
+    pub async fn advance_clock(&mut self, seconds: i64) {
+        let now = self.now().await;
+        self.set_clock(now + seconds).await;
+    }
+    pub fn set_mint(&mut self, address: Pubkey, decimals: u8) {
+        let mut data = vec![0u8; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            mint_authority: COption::Some(Pubkey::new_unique()),
+            decimals,
+            is_initialized: true,
+            ..Default::default()
+        }.pack_into_slice(&mut data);
+        self.set_account(address, spl_token::ID, data);
+    }
+    pub fn set_token_account(&mut self, address: Pubkey, owner: &Pubkey, amount: u64) {
+        let mint = self.mint;
+        self.set_token_account_with_mint(address, &mint, owner, amount);
+    }
+    pub fn set_token_account_with_mint(&mut self, address: Pubkey, mint: &Pubkey, owner: &Pubkey, amount: u64) {
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }.pack_into_slice(&mut data);
+        self.set_account(address, spl_token::ID, data);
+    }
+    pub fn set_account(&mut self, address: Pubkey, owner: Pubkey, data: Vec<u8>) {
+        let account = Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.context.set_account(&address, &AccountSharedData::from(account));
+    }
+    // A funded wallet with an associated token account holding `amount` tokens.
+    pub async fn wallet(&mut self, amount: u64) -> Keypair {
+        let wallet = Keypair::new();
+        self.context.set_account(
+            &wallet.pubkey(),
+            &AccountSharedData::new(10_000_000_000, 0, &system_program::ID),
+        );
+        let token_account = get_associated_token_address(&wallet.pubkey(), &self.mint);
+        self.set_token_account(token_account, &wallet.pubkey(), amount);
+        wallet
+    }
+    pub fn token_account(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address(owner, &self.mint)
+    }
+    pub async fn token_balance(&mut self, address: &Pubkey) -> u64 {
+        match self.context.banks_client.get_account(*address).await.unwrap() {
+            Some(account) => spl_token::state::Account::unpack(&account.data).unwrap().amount,
+            None => 0,
+        }
+    }
+    pub async fn account_exists(&mut self, address: &Pubkey) -> bool {
+        self.context.banks_client.get_account(*address).await.unwrap().is_some()
+    }
+    pub async fn account_data(&mut self, address: &Pubkey) -> Vec<u8> {
+        self.context.banks_client.get_account(*address).await.unwrap().unwrap().data
+    }
+    pub async fn stream(&mut self, address: &Pubkey) -> Stream {
+        let data = self.account_data(address).await;
+        Stream::from_versioned_bytes(&data).unwrap().0
+    }
+    // Creates and funds a stream, returning the compute units of both steps.
+    pub async fn open_stream(&mut self, stream: &StreamAccounts, employer: &Keypair, params: StreamParams, escrowed: bool) -> Result<(u64, u64), TransactionError> {
+        let total_amount = params.total_amount;
+        let created = self.process(&[stream.create(params, escrowed)], &[employer]).await?;
+        let deposited = self.process(&[stream.deposit(total_amount, escrowed)], &[employer]).await?;
+        Ok((created, deposited))
+    }
+    pub async fn multisig(&mut self, address: &Pubkey) -> StreamMultisig {
+        let data = self.account_data(address).await;
+        StreamMultisig::try_deserialize(&mut &data[..]).unwrap()
+    }
+}
+pub fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[stream_vault::ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+pub fn stream_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"stream_config"], &stream_vault::ID).0
+}
+pub fn initialize_stream_config(authority: &Pubkey, fee_recipient: &Pubkey, fee_basis_points: u16, fee_mode: FeeMode) -> Instruction {
+    Instruction {
+        program_id: stream_vault::ID,
+        accounts: stream_vault::accounts::InitializeStreamConfig {
+            config: stream_config_address(),
+            authority: *authority,
+            program_data: program_data_address(),
+            fee_recipient: *fee_recipient,
+            system_program: system_program::ID,
+        }.to_account_metas(None),
+        data: stream_vault::instruction::InitializeStreamConfig {
+            fee_basis_points,
+            fee_mode,
+            fee_exempt: vec![],
+            start_time_policy: StartTimePolicy::Strict,
+            max_slot_duration_ms: 0,
+        }.data(),
+    }
+}
+pub fn update_stream_config(
+    authority: &Pubkey,
+    fee_recipient: &Pubkey,
+    fee_basis_points: u16,
+    fee_mode: FeeMode,
+    start_time_policy: StartTimePolicy,
+    max_slot_duration_ms: u32,
+) -> Instruction {
+    Instruction {
+        program_id: stream_vault::ID,
+        accounts: stream_vault::accounts::UpdateStreamConfig {
+            config: stream_config_address(),
+            authority: *authority,
+            fee_recipient: *fee_recipient,
+        }.to_account_metas(None),
+        data: stream_vault::instruction::UpdateStreamConfig {
+            fee_basis_points,
+            fee_mode,
+            fee_exempt: vec![],
+            start_time_policy,
+            max_slot_duration_ms,
+        }.data(),
+    }
+}
+// Addresses of one stream and builders for every instruction acting on it.
+#[derive(Clone, Copy, Debug)]
+pub struct StreamAccounts {
+    pub address: Pubkey,
+    pub stream_id: u64,
+    pub employer: Pubkey,
+    pub freelancer: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+}
+pub struct StreamParams {
+    pub start_time: i64,
+    pub duration: StreamDuration,
+    pub total_amount: u64,
+    pub acceleration_type: Option<AccelerationType>,
+    pub recipients: Option<Vec<RecipientShare>>,
+}
+impl StreamParams {
+    pub fn new(start_time: i64, total_amount: u64) -> Self {
+        Self {
+            start_time,
+            duration: StreamDuration::OneWeek,
+            total_amount,
+            acceleration_type: None,
+            recipients: None,
+        }
+    }
+    pub fn accelerated(mut self, acceleration_type: AccelerationType) -> Self {
+        self.acceleration_type = Some(acceleration_type);
+        self
+    }
+    pub fn split(mut self, recipients: Vec<RecipientShare>) -> Self {
+        self.recipients = Some(recipients);
+        self
+    }
+}
+impl StreamAccounts {
+    pub fn new(employer: &Pubkey, freelancer: &Pubkey, mint: &Pubkey, stream_id: u64) -> Self {
+        let address = Pubkey::find_program_address(
+            &[b"stream", employer.as_ref(), stream_id.to_le_bytes().as_ref()],
+            &stream_vault::ID,
+        ).0;
+        Self {
+            address,
+            stream_id,
+            employer: *employer,
+            freelancer: *freelancer,
+            mint: *mint,
+            vault: get_associated_token_address(&address, mint),
+        }
+    }
+    pub fn escrow(&self) -> vault::EscrowAccounts {
+        vault::EscrowAccounts::new(&stream_vault::ID, escrow_vault_id(&self.address), &self.mint)
+    }
+    pub fn multisig(&self) -> Pubkey {
+        Pubkey::find_program_address(&[b"multisig", self.address.as_ref()], &stream_vault::ID).0
+    }
+    pub fn proposal(&self, proposal_id: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"proposal", self.address.as_ref(), proposal_id.to_le_bytes().as_ref()],
+            &stream_vault::ID,
+        ).0
+    }
+    pub fn receivable_mint(&self) -> Pubkey {
+        Pubkey::find_program_address(&[b"receivable", self.address.as_ref()], &stream_vault::ID).0
+    }
+    fn instruction(accounts: Vec<AccountMeta>, data: Vec<u8>) -> Instruction {
+        Instruction {
+            program_id: stream_vault::ID,
+            accounts,
+            data,
+        }
+    }
+    pub fn create(&self, params: StreamParams, escrowed: bool) -> Instruction {
+        let escrow = escrowed.then(|| self.escrow());
+        Self::instruction(
+            stream_vault::accounts::CreateStream {
+                stream: self.address,
+                vault: self.vault,
+                config: stream_config_address(),
+                employer: self.employer,
+                freelancer: self.freelancer,
+                mint: self.mint,
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                escrow_vault: escrow.map(|e| e.address),
+                escrow_token_account: escrow.map(|e| e.token_account),
+                escrow_config: escrow.map(|_| vault::config_address()),
+                vault_program: escrow.map(|_| VAULT_PROGRAM_ID),
+                stream_program: escrow.map(|_| stream_vault::ID),
+            }.to_account_metas(None),
+            stream_vault::instruction::CreateStream {
+                stream_id: self.stream_id,
+                start_time: params.start_time,
+                stream_duration: params.duration,
+                total_amount: params.total_amount,
+                acceleration_type: params.acceleration_type,
+                recipients: params.recipients,
+            }.data(),
+        )
+    }
+    pub fn deposit(&self, amount: u64, escrowed: bool) -> Instruction {
+        let escrow = escrowed.then(|| self.escrow());
+        Self::instruction(
+            stream_vault::accounts::DepositToEscrow {
+                stream: self.address,
+                vault: self.vault,
+                employer_token_account: get_associated_token_address(&self.employer, &self.mint),
+                fee_token_account: None,
+                employer: self.employer,
+                token_program: spl_token::ID,
+                escrow_vault: escrow.map(|e| e.address),
+                escrow_token_account: escrow.map(|e| e.token_account),
+                escrow_config: escrow.map(|_| vault::config_address()),
+                vault_program: escrow.map(|_| VAULT_PROGRAM_ID),
+                escrow_fee_token_account: None,
+                escrow_fee_ledger: None,
+            }.to_account_metas(None),
+            stream_vault::instruction::DepositToEscrow { amount }.data(),
+        )
+    }
+    pub fn withdraw(&self, recipient: &Pubkey, amount: Option<u64>, escrowed: bool) -> Instruction {
+        let escrow = escrowed.then(|| self.escrow());
+        Self::instruction(
+            stream_vault::accounts::WithdrawStreamed {
+                stream: self.address,
+                vault: self.vault,
+                freelancer_token_account: get_associated_token_address(recipient, &self.mint),
+                destination: None,
+                fee_token_account: None,
+                receivable_token_account: None,
+                freelancer: *recipient,
+                mint: self.mint,
+                token_program: spl_token::ID,
+                escrow_vault: escrow.map(|e| e.address),
+                escrow_token_account: escrow.map(|e| e.token_account),
+                vault_program: escrow.map(|_| VAULT_PROGRAM_ID),
+            }.to_account_metas(None),
+            stream_vault::instruction::WithdrawStreamed { amount }.data(),
+        )
+    }
+    pub fn pause(&self, authority: &Pubkey) -> Instruction {
+        Self::instruction(
+            stream_vault::accounts::PauseStream {
+                stream: self.address,
+                authority: *authority,
+            }.to_account_metas(None),
+            stream_vault::instruction::PauseStream {}.data(),
+        )
+    }
+    pub fn resume(&self, authority: &Pubkey) -> Instruction {
+        Self::instruction(
+            stream_vault::accounts::ResumeStream {
+                stream: self.address,
+                authority: *authority,
+            }.to_account_metas(None),
+            stream_vault::instruction::ResumeStream {}.data(),
+        )
+    }
+    // Settlement instructions take one token account per recipient, in order.
+    fn with_recipients(mut accounts: Vec<AccountMeta>, recipient_token_accounts: &[Pubkey]) -> Vec<AccountMeta> {
+        accounts.extend(recipient_token_accounts.iter().map(|account| AccountMeta::new(*account, false)));
+        accounts
+    }
+    pub fn close(&self, recipient_token_accounts: &[Pubkey], escrowed: bool) -> Instruction {
+        let escrow = escrowed.then(|| self.escrow());
+        let accounts = stream_vault::accounts::CloseStream {
+            stream: self.address,
+            vault: self.vault,
+            employer_token_account: get_associated_token_address(&self.employer, &self.mint),
+            fee_token_account: None,
+            employer: self.employer,
+            token_program: spl_token::ID,
+            escrow_vault: escrow.map(|e| e.address),
+            escrow_token_account: escrow.map(|e| e.token_account),
+            vault_program: escrow.map(|_| VAULT_PROGRAM_ID),
+        }.to_account_metas(None);
+        Self::instruction(
+            Self::with_recipients(accounts, recipient_token_accounts),
+            stream_vault::instruction::CloseStream {}.data(),
+        )
+    }
+    pub fn cancel(&self, recipient_token_accounts: &[Pubkey], escrowed: bool) -> Instruction {
+        let escrow = escrowed.then(|| self.escrow());
+        let accounts = stream_vault::accounts::CancelStream {
+            stream: self.address,
+            vault: self.vault,
+            employer_token_account: get_associated_token_address(&self.employer, &self.mint),
+            fee_token_account: None,
+            employer: self.employer,
+            token_program: spl_token::ID,
+            escrow_vault: escrow.map(|e| e.address),
+            escrow_token_account: escrow.map(|e| e.token_account),
+            vault_program: escrow.map(|_| VAULT_PROGRAM_ID),
+        }.to_account_metas(None);
+        Self::instruction(
+            Self::with_recipients(accounts, recipient_token_accounts),
+            stream_vault::instruction::CancelStream {}.data(),
+        )
+    }
+    pub fn configure_multisig(&self, signers: Vec<Pubkey>, threshold: u8) -> Instruction {
+        Self::instruction(
+            stream_vault::accounts::ConfigureMultisig {
+                stream: self.address,
+                multisig: self.multisig(),
+                employer: self.employer,
+                system_program: system_program::ID,
+            }.to_account_metas(None),
+            stream_vault::instruction::ConfigureMultisig { signers, threshold }.data(),
+        )
+    }
+    pub fn create_proposal(&self, proposer: &Pubkey, proposal_id: u64, action: StreamAction, lifetime: i64) -> Instruction {
+        Self::instruction(
+            stream_vault::accounts::CreateProposal {
+                stream: self.address,
+                multisig: self.multisig(),
+                proposal: self.proposal(proposal_id),
+                proposer: *proposer,
+                system_program: system_program::ID,
+            }.to_account_metas(None),
+            stream_vault::instruction::CreateProposal { action, lifetime }.data(),
+        )
+    }
+    pub fn approve_proposal(&self, approver: &Pubkey, proposal_id: u64) -> Instruction {
+        Self::instruction(
+            stream_vault::accounts::ApproveProposal {
+                multisig: self.multisig(),
+                proposal: self.proposal(proposal_id),
+                approver: *approver,
+            }.to_account_metas(None),
+            stream_vault::instruction::ApproveProposal {}.data(),
+        )
+    }
+    pub fn execute_proposal(&self, executor: &Pubkey, proposer: &Pubkey, proposal_id: u64, recipient_token_accounts: &[Pubkey]) -> Instruction {
+        let settles = !recipient_token_accounts.is_empty();
+        let accounts = stream_vault::accounts::ExecuteProposal {
+            stream: self.address,
+            multisig: self.multisig(),
+            proposal: self.proposal(proposal_id),
+            executor: *executor,
+            proposer: *proposer,
+            employer: self.employer,
+            vault: settles.then_some(self.vault),
+            employer_token_account: settles.then(|| get_associated_token_address(&self.employer, &self.mint)),
+            fee_token_account: None,
+            token_program: settles.then_some(spl_token::ID),
+            escrow_vault: None,
+            escrow_token_account: None,
+            vault_program: None,
+        }.to_account_metas(None);
+        Self::instruction(
+            Self::with_recipients(accounts, recipient_token_accounts),
+            stream_vault::instruction::ExecuteProposal {}.data(),
+        )
+    }
+    pub fn set_manager(&self, manager: Option<Pubkey>) -> Instruction {
+        Self::instruction(
+            stream_vault::accounts::SetStreamManager {
+                stream: self.address,
+                employer: self.employer,
+            }.to_account_metas(None),
+            stream_vault::instruction::SetStreamManager { manager }.data(),
+        )
+    }
+    pub fn quote_withdrawal(&self, recipient: &Pubkey, receivable: bool) -> Instruction {
+        Self::instruction(
+            stream_vault::accounts::QuoteWithdrawal {
+                stream: self.address,
+                mint: self.mint,
+                receivable_token_account: receivable.then(|| get_associated_token_address(recipient, &self.receivable_mint())),
+            }.to_account_metas(None),
+            stream_vault::instruction::QuoteWithdrawal { recipient: *recipient }.data(),
+        )
+    }
+    pub fn mint_receivable(&self) -> Instruction {
+        let receivable_mint = self.receivable_mint();
+        Self::instruction(
+            stream_vault::accounts::MintReceivable {
+                stream: self.address,
+                receivable_mint,
+                freelancer_receivable_account: get_associated_token_address(&self.freelancer, &receivable_mint),
+                employer: self.employer,
+                freelancer: self.freelancer,
+                system_program: system_program::ID,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+            }.to_account_metas(None),
+            stream_vault::instruction::MintReceivable {}.data(),
+        )
+    }
+    pub fn configure_renewal(&self, max_periods: u32) -> Instruction {
+        Self::instruction(
+            stream_vault::accounts::ConfigureRenewal {
+                stream: self.address,
+                employer: self.employer,
+            }.to_account_metas(None),
+            stream_vault::instruction::ConfigureRenewal { max_periods }.data(),
+        )
+    }
+    pub fn set_renewal_opt_out(&self, authority: &Pubkey, opted_out: bool) -> Instruction {
+        Self::instruction(
+            stream_vault::accounts::SetRenewalOptOut {
+                stream: self.address,
+                authority: *authority,
+            }.to_account_metas(None),
+            stream_vault::instruction::SetRenewalOptOut { opted_out }.data(),
+        )
+    }
+    pub fn roll(&self, cranker: &Pubkey, escrowed: bool) -> Instruction {
+        let escrow = escrowed.then(|| self.escrow());
+        Self::instruction(
+            stream_vault::accounts::RollStream {
+                stream: self.address,
+                vault: self.vault,
+                employer_token_account: Some(get_associated_token_address(&self.employer, &self.mint)),
+                cranker: *cranker,
+                token_program: spl_token::ID,
+                employer: escrow.map(|_| self.employer),
+                escrow_vault: escrow.map(|e| e.address),
+                escrow_token_account: escrow.map(|e| e.token_account),
+                vault_program: escrow.map(|_| VAULT_PROGRAM_ID),
+            }.to_account_metas(None),
+            stream_vault::instruction::RollStream {}.data(),
+        )
+    }
+    pub fn migrate_custody(&self) -> Instruction {
+        let escrow = self.escrow();
+        Self::instruction(
+            stream_vault::accounts::MigrateStreamCustody {
+                stream: self.address,
+                vault: self.vault,
+                employer_token_account: get_associated_token_address(&self.employer, &self.mint),
+                employer: self.employer,
+                freelancer: self.freelancer,
+                mint: self.mint,
+                escrow_vault: escrow.address,
+                escrow_token_account: escrow.token_account,
+                escrow_config: vault::config_address(),
+                vault_program: VAULT_PROGRAM_ID,
+                stream_program: stream_vault::ID,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+                escrow_fee_token_account: None,
+                escrow_fee_ledger: None,
+            }.to_account_metas(None),
+            stream_vault::instruction::MigrateStreamCustody {}.data(),
+        )
+    }
+    pub fn migrate(&self, payer: &Pubkey) -> Instruction {
+        Self::instruction(
+            stream_vault::accounts::MigrateStream {
+                stream: self.address,
+                payer: *payer,
+                system_program: system_program::ID,
+            }.to_account_metas(None),
+            stream_vault::instruction::MigrateStream {}.data(),
+        )
+    }
+}
@@ -0,0 +1,564 @@
+// Instruction builders for the `vault` program. It cannot be linked into this
+// crate, so instructions are encoded from the mirror types in `vault_cpi`.
+use anchor_lang::prelude::borsh;
+use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use solana_sdk::hash::hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_program;
+use stream_vault::constants::VAULT_PROGRAM_ID;
+use super::WEEK;
+use stream_vault::vault_cpi::{MilestoneCondition, MissedMilestoneOutcome, ReleaseAuthority, ReleaseSchedule, VaultExpiryPolicy, VaultFeeMode};
+// The sample release oracle in `programs/cliff_release_oracle`.
+pub const CLIFF_RELEASE_ORACLE_ID: Pubkey = pubkey!("FoYBmgQQHwKMRaJXcWqA2ZN7ubGydN6UKcdCVmV4FLGW");
+fn instruction_data(name: &str, args: impl FnOnce(&mut Vec<u8>)) -> Vec<u8> {
+    let mut data = hash(format!("global:{name}").as_bytes()).to_bytes()[..8].to_vec();
+    args(&mut data);
+    data
+}
+pub fn config_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"escrow_config"], &VAULT_PROGRAM_ID).0
+}
+pub fn fee_ledger_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"fee_ledger", mint.as_ref()], &VAULT_PROGRAM_ID).0
+}
+pub fn dispute_address(escrow: &EscrowAccounts) -> Pubkey {
+    Pubkey::find_program_address(&[b"dispute", escrow.address.as_ref()], &VAULT_PROGRAM_ID).0
+}
+pub fn release_proposal_address(escrow: &EscrowAccounts, proposal_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"release_proposal", escrow.address.as_ref(), proposal_id.to_le_bytes().as_ref()],
+        &VAULT_PROGRAM_ID,
+    ).0
+}
+pub fn milestone_submission_address(escrow: &EscrowAccounts, milestone_id: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"milestone_submission", escrow.address.as_ref(), milestone_id.to_le_bytes().as_ref()],
+        &VAULT_PROGRAM_ID,
+    ).0
+}
+// `count` milestones approved by `approver` that add up to exactly `total`.
+pub fn milestones(count: u32, total: u64, approver: &Pubkey) -> Vec<MilestoneCondition> {
+    let amount = total / count as u64;
+    (0..count)
+        .map(|milestone_id| MilestoneCondition {
+            milestone_id,
+            amount: if milestone_id + 1 == count { total - amount * (count as u64 - 1) } else { amount },
+            required_approval: *approver,
+            is_completed: false,
+            deadline: None,
+            missed_outcome: MissedMilestoneOutcome::RefundDepositor,
+            is_refunded: false,
+            is_submitted: false,
+        })
+        .collect()
+}
+// Mirror of `vault::state::DisputeResolution`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub enum DisputeResolution {
+    Amounts { to_beneficiary: u64, to_depositor: u64 },
+    BasisPoints { beneficiary_bps: u16 },
+}
+// Mirror of `vault::state::MilestoneReview`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub enum MilestoneReview {
+    Approve,
+    Reject { reason_hash: [u8; 32] },
+}
+#[derive(Clone, Copy, Debug)]
+pub struct EscrowAccounts {
+    pub address: Pubkey,
+    pub token_account: Pubkey,
+    pub owner_program: Pubkey,
+    pub vault_id: u64,
+    pub mint: Pubkey,
+}
+impl EscrowAccounts {
+    pub fn new(owner_program: &Pubkey, vault_id: u64, mint: &Pubkey) -> Self {
+        let address = Pubkey::find_program_address(
+            &[b"escrow_vault", owner_program.as_ref(), vault_id.to_le_bytes().as_ref()],
+            &VAULT_PROGRAM_ID,
+        ).0;
+        Self {
+            address,
+            token_account: get_associated_token_address(&address, mint),
+            owner_program: *owner_program,
+            vault_id,
+            mint: *mint,
+        }
+    }
+}
+// Mirror of `vault::state::ConfigParams`, defaulting to the settings most tests use.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ConfigParams {
+    pub fee_basis_points: u16,
+    pub fee_mode: VaultFeeMode,
+    pub min_escrow_amount: u64,
+    pub max_escrow_duration: i64,
+    pub expiry_policy: VaultExpiryPolicy,
+    pub unfunded_grace_period: i64,
+    pub milestone_review_window: i64,
+}
+impl Default for ConfigParams {
+    fn default() -> Self {
+        Self {
+            fee_basis_points: 0,
+            fee_mode: VaultFeeMode::OnTop,
+            min_escrow_amount: 1,
+            max_escrow_duration: 52 * WEEK,
+            expiry_policy: VaultExpiryPolicy::RefundAll,
+            unfunded_grace_period: WEEK,
+            milestone_review_window: WEEK / 4,
+        }
+    }
+}
+pub fn initialize_config(authority: &Pubkey, fee_recipient: &Pubkey, params: ConfigParams) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(config_address(), false),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(*fee_recipient, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data("initialize_config", |data| params.serialize(data).unwrap()),
+    }
+}
+#[allow(clippy::too_many_arguments)]
+pub fn create_escrow(
+    escrow: &EscrowAccounts,
+    depositor: &Pubkey,
+    beneficiary: &Pubkey,
+    owner_account: &Pubkey,
+    total_amount: u64,
+    release_schedule: ReleaseSchedule,
+    release_authority: ReleaseAuthority,
+    arbiter: Option<Pubkey>,
+) -> Instruction {
+    create_expiring_escrow(
+        escrow,
+        depositor,
+        beneficiary,
+        owner_account,
+        total_amount,
+        release_schedule,
+        release_authority,
+        None,
+        arbiter,
+    )
+}
+#[allow(clippy::too_many_arguments)]
+pub fn create_expiring_escrow(
+    escrow: &EscrowAccounts,
+    depositor: &Pubkey,
+    beneficiary: &Pubkey,
+    owner_account: &Pubkey,
+    total_amount: u64,
+    release_schedule: ReleaseSchedule,
+    release_authority: ReleaseAuthority,
+    expires_at: Option<i64>,
+    arbiter: Option<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new(escrow.token_account, false),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new(*depositor, true),
+            AccountMeta::new_readonly(*beneficiary, false),
+            AccountMeta::new_readonly(escrow.owner_program, false),
+            AccountMeta::new_readonly(*owner_account, false),
+            AccountMeta::new_readonly(escrow.mint, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(anchor_spl::associated_token::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data("create_escrow", |data| {
+            escrow.vault_id.serialize(data).unwrap();
+            total_amount.serialize(data).unwrap();
+            release_schedule.serialize(data).unwrap();
+            release_authority.serialize(data).unwrap();
+            expires_at.serialize(data).unwrap();
+            arbiter.serialize(data).unwrap();
+        }),
+    }
+}
+pub fn update_config(authority: &Pubkey, fee_recipient: &Pubkey, params: ConfigParams) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(config_address(), false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(*fee_recipient, false),
+        ],
+        data: instruction_data("update_config", |data| params.serialize(data).unwrap()),
+    }
+}
+pub fn set_config_paused(authority: &Pubkey, paused: bool) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(config_address(), false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: instruction_data("set_config_paused", |data| paused.serialize(data).unwrap()),
+    }
+}
+pub fn propose_config_authority(authority: &Pubkey, new_authority: Option<Pubkey>) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(config_address(), false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: instruction_data("propose_config_authority", |data| new_authority.serialize(data).unwrap()),
+    }
+}
+pub fn accept_config_authority(new_authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(config_address(), false),
+            AccountMeta::new_readonly(*new_authority, true),
+        ],
+        data: instruction_data("accept_config_authority", |_| {}),
+    }
+}
+pub fn initialize_fee_ledger(mint: &Pubkey, payer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(fee_ledger_address(mint), false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data("initialize_fee_ledger", |_| {}),
+    }
+}
+pub fn deposit_funds(escrow: &EscrowAccounts, depositor: &Pubkey, amount: u64) -> Instruction {
+    // Without a fee the optional fee accounts are passed as the program id.
+    let no_fee_accounts = [
+        AccountMeta::new_readonly(VAULT_PROGRAM_ID, false),
+        AccountMeta::new_readonly(VAULT_PROGRAM_ID, false),
+    ];
+    deposit(escrow, depositor, amount, no_fee_accounts)
+}
+fn deposit(escrow: &EscrowAccounts, depositor: &Pubkey, amount: u64, [fee_token_account, fee_ledger]: [AccountMeta; 2]) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new(escrow.token_account, false),
+            AccountMeta::new(*depositor, true),
+            AccountMeta::new(get_associated_token_address(depositor, &escrow.mint), false),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            fee_token_account,
+            fee_ledger,
+        ],
+        data: instruction_data("deposit_funds", |data| amount.serialize(data).unwrap()),
+    }
+}
+pub fn withdraw_available(escrow: &EscrowAccounts, withdrawer: &Pubkey, max_amount: Option<u64>) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new(escrow.token_account, false),
+            AccountMeta::new(*withdrawer, true),
+            AccountMeta::new(get_associated_token_address(withdrawer, &escrow.mint), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: instruction_data("withdraw_available", |data| max_amount.serialize(data).unwrap()),
+    }
+}
+pub fn close_escrow(escrow: &EscrowAccounts, authority: &Pubkey, depositor: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new(escrow.token_account, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*depositor, false),
+            AccountMeta::new(get_associated_token_address(depositor, &escrow.mint), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: instruction_data("close_escrow", |_| {}),
+    }
+}
+pub fn update_release_schedule(escrow: &EscrowAccounts, authority: &Pubkey, new_schedule: ReleaseSchedule) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(config_address(), false),
+        ],
+        data: instruction_data("update_release_schedule", |data| new_schedule.serialize(data).unwrap()),
+    }
+}
+pub fn release_milestone(escrow: &EscrowAccounts, authority: &Pubkey, milestone_id: u32) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: instruction_data("release_milestone", |data| milestone_id.serialize(data).unwrap()),
+    }
+}
+pub fn migrate_escrow_vault(escrow: &EscrowAccounts, payer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data("migrate_escrow_vault", |_| {}),
+    }
+}
+
+pub fn propose_release(escrow: &EscrowAccounts, proposer: &Pubkey, proposal_id: u64, amount: u64, lifetime: i64) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(escrow.address, false),
+            AccountMeta::new(release_proposal_address(escrow, proposal_id), false),
+            AccountMeta::new(*proposer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data("propose_release", |data| (proposal_id, amount, lifetime).serialize(data).unwrap()),
+    }
+}
+pub fn approve_release(escrow: &EscrowAccounts, approver: &Pubkey, proposal_id: u64) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(escrow.address, false),
+            AccountMeta::new(release_proposal_address(escrow, proposal_id), false),
+            AccountMeta::new_readonly(*approver, true),
+        ],
+        data: instruction_data("approve_release", |_| {}),
+    }
+}
+pub fn execute_release(
+    escrow: &EscrowAccounts,
+    executor: &Pubkey,
+    proposer: &Pubkey,
+    beneficiary: &Pubkey,
+    proposal_id: u64,
+) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new(escrow.token_account, false),
+            AccountMeta::new(release_proposal_address(escrow, proposal_id), false),
+            AccountMeta::new(*proposer, false),
+            AccountMeta::new_readonly(*executor, true),
+            AccountMeta::new(get_associated_token_address(beneficiary, &escrow.mint), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: instruction_data("execute_release", |_| {}),
+    }
+}
+pub fn cancel_release(escrow: &EscrowAccounts, authority: &Pubkey, proposer: &Pubkey, proposal_id: u64) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(escrow.address, false),
+            AccountMeta::new(release_proposal_address(escrow, proposal_id), false),
+            AccountMeta::new(*proposer, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: instruction_data("cancel_release", |_| {}),
+    }
+}
+pub fn raise_dispute(escrow: &EscrowAccounts, raised_by: &Pubkey, amount: u64, evidence_hash: [u8; 32]) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new_readonly(escrow.token_account, false),
+            AccountMeta::new(dispute_address(escrow), false),
+            AccountMeta::new(*raised_by, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data("raise_dispute", |data| (amount, evidence_hash).serialize(data).unwrap()),
+    }
+}
+pub fn resolve_dispute(
+    escrow: &EscrowAccounts,
+    arbiter: &Pubkey,
+    raised_by: &Pubkey,
+    beneficiary: &Pubkey,
+    depositor: &Pubkey,
+    resolution: DisputeResolution,
+) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new(escrow.token_account, false),
+            AccountMeta::new(dispute_address(escrow), false),
+            AccountMeta::new(*raised_by, false),
+            AccountMeta::new_readonly(*arbiter, true),
+            AccountMeta::new(get_associated_token_address(beneficiary, &escrow.mint), false),
+            AccountMeta::new(get_associated_token_address(depositor, &escrow.mint), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: instruction_data("resolve_dispute", |data| resolution.serialize(data).unwrap()),
+    }
+}
+// `beneficiary_consents` adds the beneficiary as a co-signer.
+pub fn cancel_escrow(
+    escrow: &EscrowAccounts,
+    authority: &Pubkey,
+    beneficiary: &Pubkey,
+    depositor: &Pubkey,
+    beneficiary_consents: bool,
+) -> Instruction {
+    let beneficiary_consent = if beneficiary_consents {
+        AccountMeta::new_readonly(*beneficiary, true)
+    } else {
+        AccountMeta::new_readonly(VAULT_PROGRAM_ID, false)
+    };
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new(escrow.token_account, false),
+            AccountMeta::new_readonly(*authority, true),
+            beneficiary_consent,
+            AccountMeta::new(get_associated_token_address(beneficiary, &escrow.mint), false),
+            AccountMeta::new(get_associated_token_address(depositor, &escrow.mint), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: instruction_data("cancel_escrow", |_| {}),
+    }
+}
+pub fn reclaim_expired(escrow: &EscrowAccounts, beneficiary: &Pubkey, depositor: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new(escrow.token_account, false),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new(get_associated_token_address(beneficiary, &escrow.mint), false),
+            AccountMeta::new(get_associated_token_address(depositor, &escrow.mint), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: instruction_data("reclaim_expired", |_| {}),
+    }
+}
+pub fn close_stale_escrow(escrow: &EscrowAccounts, depositor: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new(escrow.token_account, false),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new(*depositor, false),
+            AccountMeta::new(get_associated_token_address(depositor, &escrow.mint), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: instruction_data("close_stale_escrow", |_| {}),
+    }
+}
+pub fn pause_escrow(escrow: &EscrowAccounts, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: instruction_data("pause_escrow", |_| {}),
+    }
+}
+pub fn resume_escrow(escrow: &EscrowAccounts, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new_readonly(escrow.token_account, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: instruction_data("resume_escrow", |_| {}),
+    }
+}
+pub fn refund_missed_milestones(escrow: &EscrowAccounts, depositor: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new(escrow.token_account, false),
+            AccountMeta::new(get_associated_token_address(depositor, &escrow.mint), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data: instruction_data("refund_missed_milestones", |_| {}),
+    }
+}
+pub fn submit_milestone(escrow: &EscrowAccounts, beneficiary: &Pubkey, milestone_id: u32, deliverable_hash: [u8; 32]) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new(milestone_submission_address(escrow, milestone_id), false),
+            AccountMeta::new(*beneficiary, true),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data("submit_milestone", |data| (milestone_id, deliverable_hash).serialize(data).unwrap()),
+    }
+}
+pub fn review_milestone(
+    escrow: &EscrowAccounts,
+    approver: &Pubkey,
+    submitter: &Pubkey,
+    milestone_id: u32,
+    review: MilestoneReview,
+) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new(milestone_submission_address(escrow, milestone_id), false),
+            AccountMeta::new(*submitter, false),
+            AccountMeta::new_readonly(*approver, true),
+        ],
+        data: instruction_data("review_milestone", |data| review.serialize(data).unwrap()),
+    }
+}
+pub fn finalize_milestone(escrow: &EscrowAccounts, submitter: &Pubkey, milestone_id: u32) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(escrow.address, false),
+            AccountMeta::new(milestone_submission_address(escrow, milestone_id), false),
+            AccountMeta::new(*submitter, false),
+        ],
+        data: instruction_data("finalize_milestone", |_| {}),
+    }
+}
+pub fn migrate_config(authority: &Pubkey, params: ConfigParams) -> Instruction {
+    Instruction {
+        program_id: VAULT_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(config_address(), false),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data("migrate_config", |data| params.serialize(data).unwrap()),
+    }
+}
+// Oracle schedules are priced by their oracle, passed after the declared accounts.
+pub fn with_release_oracle(mut instruction: Instruction, oracle_program: &Pubkey) -> Instruction {
+    instruction.accounts.push(AccountMeta::new_readonly(*oracle_program, false));
+    instruction
+}
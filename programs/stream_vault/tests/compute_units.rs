@@ -0,0 +1,548 @@
+// Compute-unit benchmark for every instruction of `stream_vault` and `vault`,
+// measured on the SBF builds and checked against `compute_units.baseline`.
+// Rewrite the baseline with `UPDATE_CU_BASELINE=1 cargo test --test compute_units -- --ignored`;
+// set `CU_BEFORE=<baseline of another build>` to print a before/after table.
+// Entries recorded as `-` have not been measured yet and fail the check.
+mod common;
+use std::collections::BTreeMap;
+use common::vault::{self as vault_ix, ConfigParams, DisputeResolution, EscrowAccounts, MilestoneReview};
+use common::{initialize_stream_config, update_stream_config, Harness, Runtime, StreamAccounts, StreamParams, WEEK};
+use solana_sdk::hash::hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use stream_vault::state::{FeeMode, RecipientShare, StartTimePolicy, StreamAction, StreamV1};
+use stream_vault::strategies::AccelerationType;
+use stream_vault::vault_cpi::{LinearConfig, ReleaseAuthority, ReleaseSchedule, StepEntry, VaultAccelerationType};
+use anchor_lang::{AnchorSerialize, Discriminator};
+const BASELINE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/compute_units.baseline");
+const REGRESSION_TOLERANCE_PERCENT: u64 = 5;
+const TOTAL: u64 = 1_000_000_000;
+// vault's `MAX_MILESTONES` and `MAX_HYBRID_MILESTONES`, the most the schedule space holds.
+const MAX_MILESTONES: u32 = 4;
+const MAX_HYBRID_MILESTONES: u32 = 3;
+#[derive(Default)]
+struct Bench {
+    measured: Vec<(String, u64)>,
+}
+impl Bench {
+    fn record(&mut self, name: &str, units: u64) {
+        println!("{name:<56} {units:>8}");
+        self.measured.push((name.to_string(), units));
+    }
+    fn finish(self) {
+        if std::env::var("UPDATE_CU_BASELINE").is_ok() {
+            let mut contents = String::from(
+                "# Compute units per instruction, measured from the SBF builds.\n\
+                 # Regenerate with `UPDATE_CU_BASELINE=1 cargo test --test compute_units -- --ignored`.\n",
+            );
+            for (name, units) in &self.measured {
+                contents.push_str(&format!("{name} {units}\n"));
+            }
+            std::fs::write(BASELINE_PATH, contents).unwrap();
+            return;
+        }
+        if let Ok(before_path) = std::env::var("CU_BEFORE") {
+            self.print_comparison(&read_baseline(&before_path));
+        }
+        let baseline = read_baseline(BASELINE_PATH);
+        let mut regressions = Vec::new();
+        let mut missing = Vec::new();
+        for (name, units) in &self.measured {
+            match baseline.get(name) {
+                Some(&Some(expected)) if units * 100 > expected * (100 + REGRESSION_TOLERANCE_PERCENT) => {
+                    regressions.push(format!("{name}: {units} CU (baseline {expected} CU)"));
+                },
+                Some(Some(_)) => {},
+                Some(None) | None => missing.push(name.as_str()),
+            }
+        }
+        assert!(
+            missing.is_empty(),
+            "no baseline recorded for:\n{}\nregenerate it with `UPDATE_CU_BASELINE=1 cargo test --test compute_units -- --ignored`",
+            missing.join("\n")
+        );
+        assert!(
+            regressions.is_empty(),
+            "compute units regressed more than {}%:\n{}",
+            REGRESSION_TOLERANCE_PERCENT,
+            regressions.join("\n")
+        );
+    }
+    // Markdown before/after table against a baseline recorded from another build.
+    fn print_comparison(&self, before: &BTreeMap<String, Option<u64>>) {
+        println!("| Instruction | Before | After | Change |");
+        println!("|---|---:|---:|---:|");
+        for (name, units) in &self.measured {
+            match before.get(name) {
+                Some(&Some(before)) => {
+                    let change = (*units as f64 - before as f64) * 100.0 / before as f64;
+                    println!("| `{name}` | {before} | {units} | {change:+.1}% |");
+                },
+                _ => println!("| `{name}` | n/a | {units} | |"),
+            }
+        }
+    }
+}
+fn read_baseline(path: &str) -> BTreeMap<String, Option<u64>> {
+    std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (name, units) = line.rsplit_once(' ').expect("baseline lines are `<name> <units>`");
+            let units = match units.trim() {
+                "-" => None,
+                units => Some(units.parse().expect("baseline units must be a number or `-`")),
+            };
+            (name.to_string(), units)
+        })
+        .collect()
+}
+async fn bench_stream_vault(bench: &mut Bench) {
+    let mut h = Harness::new(Runtime::Sbf).await;
+    let payer = h.payer();
+    let units = h.process(
+        &[initialize_stream_config(&payer.pubkey(), &h.fee_recipient, 0, FeeMode::OnWithdrawal)],
+        &[],
+    ).await.unwrap();
+    bench.record("stream_vault::initialize_stream_config", units);
+    let units = h.process(
+        &[update_stream_config(&payer.pubkey(), &h.fee_recipient, 0, FeeMode::OnWithdrawal, StartTimePolicy::Strict, 0)],
+        &[],
+    ).await.unwrap();
+    bench.record("stream_vault::update_stream_config", units);
+    h.initialize_vault_config().await;
+
+    let employer = h.wallet(100 * TOTAL).await;
+    let freelancer = h.wallet(0).await;
+    let mut recipients = Vec::new();
+    for _ in 0..5 {
+        recipients.push(h.wallet(0).await);
+    }
+    let mint = h.mint;
+    let start = h.now().await + 60;
+    let stream = |stream_id| StreamAccounts::new(&employer.pubkey(), &freelancer.pubkey(), &mint, stream_id);
+
+    let linear = stream(1);
+    let (created, deposited) = h.open_stream(&linear, &employer, StreamParams::new(start, TOTAL), false).await.unwrap();
+    bench.record("stream_vault::create_stream/linear", created);
+    bench.record("stream_vault::deposit_to_escrow", deposited);
+    let split = StreamAccounts::new(&employer.pubkey(), &recipients[0].pubkey(), &mint, 2);
+    let shares = recipients
+        .iter()
+        .map(|recipient| RecipientShare { recipient: recipient.pubkey(), share_bps: 2_000 })
+        .collect();
+    let params = StreamParams::new(start, TOTAL).accelerated(AccelerationType::Cubic).split(shares);
+    let (created, _) = h.open_stream(&split, &employer, params, false).await.unwrap();
+    bench.record("stream_vault::create_stream/cubic_5_recipients", created);
+    let escrowed = stream(3);
+    let (created, deposited) = h.open_stream(&escrowed, &employer, StreamParams::new(start, TOTAL), true).await.unwrap();
+    bench.record("stream_vault::create_stream/escrowed", created);
+    bench.record("stream_vault::deposit_to_escrow/escrowed", deposited);
+    let receivable = stream(4);
+    h.open_stream(&receivable, &employer, StreamParams::new(start, TOTAL), false).await.unwrap();
+    let governed = stream(5);
+    h.open_stream(&governed, &employer, StreamParams::new(start, TOTAL), false).await.unwrap();
+    let custodied = stream(6);
+    h.open_stream(&custodied, &employer, StreamParams::new(start, TOTAL), false).await.unwrap();
+    let cancelled = stream(7);
+    h.open_stream(&cancelled, &employer, StreamParams::new(start, TOTAL), false).await.unwrap();
+
+    h.set_clock(start + WEEK / 2).await;
+    let units = h.process(&[linear.quote_withdrawal(&freelancer.pubkey(), false)], &[]).await.unwrap();
+    bench.record("stream_vault::quote_withdrawal", units);
+    let units = h.process(&[linear.withdraw(&freelancer.pubkey(), None, false)], &[&freelancer]).await.unwrap();
+    bench.record("stream_vault::withdraw_streamed/linear", units);
+    let last = &recipients[4];
+    let units = h.process(&[split.withdraw(&last.pubkey(), None, false)], &[last]).await.unwrap();
+    bench.record("stream_vault::withdraw_streamed/cubic_5_recipients", units);
+    let units = h.process(&[escrowed.withdraw(&freelancer.pubkey(), None, true)], &[&freelancer]).await.unwrap();
+    bench.record("stream_vault::withdraw_streamed/escrowed", units);
+
+    let units = h.process(&[linear.pause(&employer.pubkey())], &[&employer]).await.unwrap();
+    bench.record("stream_vault::pause_stream", units);
+    h.advance_clock(60).await;
+    let units = h.process(&[linear.resume(&employer.pubkey())], &[&employer]).await.unwrap();
+    bench.record("stream_vault::resume_stream", units);
+    let units = h.process(&[linear.set_manager(Some(Pubkey::new_unique()))], &[&employer]).await.unwrap();
+    bench.record("stream_vault::set_stream_manager", units);
+    let units = h.process(&[linear.configure_renewal(2)], &[&employer]).await.unwrap();
+    bench.record("stream_vault::configure_renewal", units);
+    let units = h.process(&[linear.set_renewal_opt_out(&freelancer.pubkey(), false)], &[&freelancer]).await.unwrap();
+    bench.record("stream_vault::set_renewal_opt_out", units);
+    let units = h.process(&[receivable.mint_receivable()], &[&employer]).await.unwrap();
+    bench.record("stream_vault::mint_receivable", units);
+
+    let cosigner = Keypair::new();
+    let signers = vec![employer.pubkey(), cosigner.pubkey()];
+    let units = h.process(&[governed.configure_multisig(signers, 2)], &[&employer]).await.unwrap();
+    bench.record("stream_vault::configure_multisig", units);
+    let units = h.process(
+        &[governed.create_proposal(&employer.pubkey(), 0, StreamAction::Pause, WEEK)],
+        &[&employer],
+    ).await.unwrap();
+    bench.record("stream_vault::create_proposal", units);
+    let units = h.process(&[governed.approve_proposal(&cosigner.pubkey(), 0)], &[&cosigner]).await.unwrap();
+    bench.record("stream_vault::approve_proposal", units);
+    let units = h.process(&[governed.execute_proposal(&employer.pubkey(), &employer.pubkey(), 0, &[])], &[&employer]).await.unwrap();
+    bench.record("stream_vault::execute_proposal/pause", units);
+
+    let units = h.process(&[custodied.migrate_custody()], &[&employer]).await.unwrap();
+    bench.record("stream_vault::migrate_stream_custody", units);
+    let legacy = stream(8);
+    let mut data = stream_vault::state::Stream::DISCRIMINATOR.to_vec();
+    StreamV1 {
+        employer: legacy.employer,
+        freelancer: legacy.freelancer,
+        stream_id: legacy.stream_id,
+        start_time: start,
+        end_time: start + WEEK,
+        total_amount: TOTAL,
+        withdrawn_amount: 0,
+        is_paused: false,
+        pause_time: None,
+        total_paused_duration: 0,
+        pause_resume_count: 0,
+        last_operation_slot: 0,
+        accumulated_dust: 0,
+        mint,
+        vault: legacy.vault,
+        acceleration_type: AccelerationType::Linear,
+        bump: 255,
+        is_funded: false,
+    }.serialize(&mut data).unwrap();
+    data.resize(StreamV1::LEN, 0);
+    h.set_account(legacy.address, stream_vault::ID, data);
+    let units = h.process(&[legacy.migrate(&payer.pubkey())], &[]).await.unwrap();
+    bench.record("stream_vault::migrate_stream", units);
+
+    let recipient_accounts: Vec<Pubkey> = recipients.iter().map(|r| h.token_account(&r.pubkey())).collect();
+    let freelancer_account = h.token_account(&freelancer.pubkey());
+    let units = h.process(&[cancelled.cancel(&[freelancer_account], false)], &[&employer]).await.unwrap();
+    bench.record("stream_vault::cancel_stream", units);
+
+    h.set_clock(start + 2 * WEEK).await;
+    let outstanding = TOTAL - h.stream(&linear.address).await.withdrawn_amount;
+    h.set_token_account(linear.vault, &linear.address, outstanding + TOTAL);
+    let units = h.process(&[linear.roll(&payer.pubkey(), false)], &[]).await.unwrap();
+    bench.record("stream_vault::roll_stream", units);
+    let units = h.process(&[split.close(&recipient_accounts, false)], &[&employer]).await.unwrap();
+    bench.record("stream_vault::close_stream/cubic_5_recipients", units);
+    let units = h.process(&[escrowed.close(&[freelancer_account], true)], &[&employer]).await.unwrap();
+    bench.record("stream_vault::close_stream/escrowed", units);
+}
+async fn bench_vault(bench: &mut Bench) {
+    let mut h = Harness::new(Runtime::Sbf).await;
+    let units = h.initialize_vault_config().await;
+    bench.record("vault::initialize_config", units);
+    let payer = h.payer();
+    let fee_recipient = h.fee_recipient;
+    let ix = vault_ix::update_config(&payer.pubkey(), &fee_recipient, ConfigParams::default());
+    let units = h.process(&[ix], &[]).await.unwrap();
+    bench.record("vault::update_config", units);
+    let units = h.process(&[vault_ix::set_config_paused(&payer.pubkey(), false)], &[]).await.unwrap();
+    bench.record("vault::set_config_paused", units);
+    let units = h.process(&[vault_ix::propose_config_authority(&payer.pubkey(), Some(payer.pubkey()))], &[]).await.unwrap();
+    bench.record("vault::propose_config_authority", units);
+    let units = h.process(&[vault_ix::accept_config_authority(&payer.pubkey())], &[]).await.unwrap();
+    bench.record("vault::accept_config_authority", units);
+    let units = h.process(&[vault_ix::initialize_fee_ledger(&h.mint, &payer.pubkey())], &[]).await.unwrap();
+    bench.record("vault::initialize_fee_ledger", units);
+    // Version 1 configs predate the layout version and every setting after
+    // the duration limit.
+    let bump = *h.account_data(&vault_ix::config_address()).await.last().unwrap();
+    let mut legacy = hash(b"account:EscrowConfig").to_bytes()[..8].to_vec();
+    (payer.pubkey(), 0u16, fee_recipient, 1u64, 52 * WEEK, false, 4u32, bump).serialize(&mut legacy).unwrap();
+    h.set_account(vault_ix::config_address(), stream_vault::constants::VAULT_PROGRAM_ID, legacy);
+    let units = h.process(&[vault_ix::migrate_config(&payer.pubkey(), ConfigParams::default())], &[]).await.unwrap();
+    bench.record("vault::migrate_config", units);
+    let depositor = h.wallet(100 * TOTAL).await;
+    let beneficiary = h.wallet(0).await;
+    let approver = Keypair::new();
+    let owner_program = Pubkey::new_unique();
+    let start = h.now().await;
+    let end = start + WEEK;
+    let schedules = [
+        ("immediate", ReleaseSchedule::Immediate),
+        ("linear", ReleaseSchedule::Linear { start, end }),
+        ("milestone_4", ReleaseSchedule::Milestone {
+            conditions: vault_ix::milestones(MAX_MILESTONES, TOTAL, &approver.pubkey()),
+        }),
+        ("hybrid_3", ReleaseSchedule::Hybrid {
+            linear_portion: TOTAL / 2,
+            milestone_portion: TOTAL / 2,
+            linear_config: LinearConfig {
+                start_time: start,
+                end_time: end,
+                acceleration_type: VaultAccelerationType::Quadratic,
+            },
+            milestone_config: vault_ix::milestones(MAX_HYBRID_MILESTONES, TOTAL / 2, &approver.pubkey()),
+        }),
+        ("steps_12", ReleaseSchedule::Steps {
+            entries: (1..=12)
+                .map(|step| StepEntry { timestamp: start + step * WEEK / 24, cumulative_amount: step as u64 * TOTAL / 12 })
+                .collect(),
+        }),
+        ("oracle_cliff", ReleaseSchedule::Oracle {
+            oracle_program: vault_ix::CLIFF_RELEASE_ORACLE_ID,
+            data: (start, end, 2_000u16).try_to_vec().unwrap(),
+        }),
+    ];
+    let mut escrows = Vec::new();
+    for (vault_id, (name, schedule)) in schedules.into_iter().enumerate() {
+        let escrow = EscrowAccounts::new(&owner_program, vault_id as u64, &h.mint);
+        let units = h.process(
+            &[vault_ix::create_escrow(
+                &escrow,
+                &depositor.pubkey(),
+                &beneficiary.pubkey(),
+                &Pubkey::new_unique(),
+                TOTAL,
+                schedule,
+                ReleaseAuthority::Beneficiary,
+                None,
+            )],
+            &[&depositor],
+        ).await.unwrap();
+        bench.record(&format!("vault::create_escrow/{name}"), units);
+        let units = h.process(&[vault_ix::deposit_funds(&escrow, &depositor.pubkey(), TOTAL)], &[&depositor]).await.unwrap();
+        bench.record(&format!("vault::deposit_funds/{name}"), units);
+        escrows.push((name, escrow));
+    }
+
+    h.set_clock(start + WEEK / 2).await;
+    for (name, escrow) in &escrows[2..4] {
+        let count = if *name == "milestone_4" { MAX_MILESTONES } else { MAX_HYBRID_MILESTONES };
+        for milestone_id in 0..count {
+            let units = h.process(&[vault_ix::release_milestone(escrow, &approver.pubkey(), milestone_id)], &[&approver]).await.unwrap();
+            if milestone_id + 1 == count {
+                bench.record(&format!("vault::release_milestone/{name}"), units);
+            }
+        }
+    }
+    for (name, escrow) in &escrows {
+        let mut ix = vault_ix::withdraw_available(escrow, &beneficiary.pubkey(), None);
+        if *name == "oracle_cliff" {
+            ix = vault_ix::with_release_oracle(ix, &vault_ix::CLIFF_RELEASE_ORACLE_ID);
+        }
+        let units = h.process(&[ix], &[&beneficiary]).await.unwrap();
+        bench.record(&format!("vault::withdraw_available/{name}"), units);
+    }
+
+    let escrow = EscrowAccounts::new(&owner_program, escrows.len() as u64, &h.mint);
+    h.process(
+        &[vault_ix::create_escrow(
+            &escrow,
+            &depositor.pubkey(),
+            &beneficiary.pubkey(),
+            &Pubkey::new_unique(),
+            TOTAL,
+            ReleaseSchedule::Linear { start, end },
+            ReleaseAuthority::Beneficiary,
+            None,
+        )],
+        &[&depositor],
+    ).await.unwrap();
+    h.process(&[vault_ix::deposit_funds(&escrow, &depositor.pubkey(), TOTAL)], &[&depositor]).await.unwrap();
+
+    // Version 3 only differs from the current layout in how milestones are
+    // stored, so a linear escrow relabelled as v3 decodes as one.
+    let mut legacy = h.account_data(&escrow.address).await;
+    legacy[8] = 3;
+    h.set_account(escrow.address, stream_vault::constants::VAULT_PROGRAM_ID, legacy);
+    let units = h.process(&[vault_ix::migrate_escrow_vault(&escrow, &payer.pubkey())], &[]).await.unwrap();
+    bench.record("vault::migrate_escrow_vault", units);
+    let new_schedule = ReleaseSchedule::Milestone {
+        conditions: vault_ix::milestones(MAX_MILESTONES, TOTAL, &approver.pubkey()),
+    };
+    let units = h.process(&[vault_ix::update_release_schedule(&escrow, &depositor.pubkey(), new_schedule)], &[&depositor]).await.unwrap();
+    bench.record("vault::update_release_schedule/milestone_4", units);
+    let units = h.process(&[vault_ix::close_escrow(&escrow, &depositor.pubkey(), &depositor.pubkey())], &[&depositor]).await.unwrap();
+    bench.record("vault::close_escrow", units);
+
+    let escrow = EscrowAccounts::new(&owner_program, escrows.len() as u64 + 1, &h.mint);
+    h.process(
+        &[
+            vault_ix::create_escrow(
+                &escrow,
+                &depositor.pubkey(),
+                &beneficiary.pubkey(),
+                &Pubkey::new_unique(),
+                TOTAL,
+                ReleaseSchedule::Immediate,
+                ReleaseAuthority::Both,
+                None,
+            ),
+            vault_ix::deposit_funds(&escrow, &depositor.pubkey(), TOTAL),
+        ],
+        &[&depositor],
+    ).await.unwrap();
+    let units = h.process(&[vault_ix::propose_release(&escrow, &beneficiary.pubkey(), 0, TOTAL / 2, WEEK)], &[&beneficiary]).await.unwrap();
+    bench.record("vault::propose_release", units);
+    let units = h.process(&[vault_ix::approve_release(&escrow, &depositor.pubkey(), 0)], &[&depositor]).await.unwrap();
+    bench.record("vault::approve_release", units);
+    let ix = vault_ix::execute_release(&escrow, &depositor.pubkey(), &beneficiary.pubkey(), &beneficiary.pubkey(), 0);
+    let units = h.process(&[ix], &[&depositor]).await.unwrap();
+    bench.record("vault::execute_release", units);
+    h.process(&[vault_ix::propose_release(&escrow, &beneficiary.pubkey(), 1, TOTAL / 2, WEEK)], &[&beneficiary]).await.unwrap();
+    let ix = vault_ix::cancel_release(&escrow, &depositor.pubkey(), &beneficiary.pubkey(), 1);
+    let units = h.process(&[ix], &[&depositor]).await.unwrap();
+    bench.record("vault::cancel_release", units);
+
+    let escrow = EscrowAccounts::new(&owner_program, escrows.len() as u64 + 2, &h.mint);
+    h.process(
+        &[
+            vault_ix::create_escrow(
+                &escrow,
+                &depositor.pubkey(),
+                &beneficiary.pubkey(),
+                &Pubkey::new_unique(),
+                TOTAL,
+                ReleaseSchedule::Linear { start, end },
+                ReleaseAuthority::Beneficiary,
+                Some(approver.pubkey()),
+            ),
+            vault_ix::deposit_funds(&escrow, &depositor.pubkey(), TOTAL),
+        ],
+        &[&depositor],
+    ).await.unwrap();
+    let units = h.process(&[vault_ix::raise_dispute(&escrow, &depositor.pubkey(), TOTAL, [1; 32])], &[&depositor]).await.unwrap();
+    bench.record("vault::raise_dispute", units);
+    let resolution = DisputeResolution::BasisPoints { beneficiary_bps: 5_000 };
+    let ix = vault_ix::resolve_dispute(&escrow, &approver.pubkey(), &depositor.pubkey(), &beneficiary.pubkey(), &depositor.pubkey(), resolution);
+    let units = h.process(&[ix], &[&approver]).await.unwrap();
+    bench.record("vault::resolve_dispute", units);
+
+    let escrow = EscrowAccounts::new(&owner_program, escrows.len() as u64 + 3, &h.mint);
+    h.process(
+        &[
+            vault_ix::create_escrow(
+                &escrow,
+                &depositor.pubkey(),
+                &beneficiary.pubkey(),
+                &Pubkey::new_unique(),
+                TOTAL,
+                ReleaseSchedule::Linear { start, end },
+                ReleaseAuthority::Beneficiary,
+                None,
+            ),
+            vault_ix::deposit_funds(&escrow, &depositor.pubkey(), TOTAL),
+        ],
+        &[&depositor],
+    ).await.unwrap();
+    let ix = vault_ix::cancel_escrow(&escrow, &depositor.pubkey(), &beneficiary.pubkey(), &depositor.pubkey(), true);
+    let units = h.process(&[ix], &[&depositor, &beneficiary]).await.unwrap();
+    bench.record("vault::cancel_escrow", units);
+
+    let escrow = EscrowAccounts::new(&owner_program, escrows.len() as u64 + 4, &h.mint);
+    let create = vault_ix::create_expiring_escrow(
+        &escrow,
+        &depositor.pubkey(),
+        &beneficiary.pubkey(),
+        &Pubkey::new_unique(),
+        TOTAL,
+        ReleaseSchedule::Linear { start, end },
+        ReleaseAuthority::Beneficiary,
+        Some(start + WEEK),
+        None,
+    );
+    let deposit = vault_ix::deposit_funds(&escrow, &depositor.pubkey(), TOTAL);
+    h.process(&[create, deposit], &[&depositor]).await.unwrap();
+    let stale = EscrowAccounts::new(&owner_program, escrows.len() as u64 + 5, &h.mint);
+    let create = vault_ix::create_escrow(
+        &stale,
+        &depositor.pubkey(),
+        &beneficiary.pubkey(),
+        &Pubkey::new_unique(),
+        TOTAL,
+        ReleaseSchedule::Immediate,
+        ReleaseAuthority::Beneficiary,
+        None,
+    );
+    h.process(&[create], &[&depositor]).await.unwrap();
+    h.set_clock(start + 2 * WEEK).await;
+    let units = h.process(&[vault_ix::reclaim_expired(&escrow, &beneficiary.pubkey(), &depositor.pubkey())], &[]).await.unwrap();
+    bench.record("vault::reclaim_expired", units);
+    let units = h.process(&[vault_ix::close_stale_escrow(&stale, &depositor.pubkey())], &[]).await.unwrap();
+    bench.record("vault::close_stale_escrow", units);
+
+    let escrow = EscrowAccounts::new(&owner_program, escrows.len() as u64 + 6, &h.mint);
+    let create = vault_ix::create_escrow(
+        &escrow,
+        &depositor.pubkey(),
+        &beneficiary.pubkey(),
+        &Pubkey::new_unique(),
+        TOTAL,
+        ReleaseSchedule::Linear { start: start + 2 * WEEK, end: start + 3 * WEEK },
+        ReleaseAuthority::Beneficiary,
+        None,
+    );
+    let deposit = vault_ix::deposit_funds(&escrow, &depositor.pubkey(), TOTAL);
+    h.process(&[create, deposit], &[&depositor]).await.unwrap();
+    let units = h.process(&[vault_ix::pause_escrow(&escrow, &depositor.pubkey())], &[&depositor]).await.unwrap();
+    bench.record("vault::pause_escrow", units);
+    h.advance_clock(60).await;
+    let units = h.process(&[vault_ix::resume_escrow(&escrow, &depositor.pubkey())], &[&depositor]).await.unwrap();
+    bench.record("vault::resume_escrow", units);
+
+    // One milestone approved on review, one finalized once its review window
+    // passes and two missed and refunded to the depositor.
+    let now = h.now().await;
+    let mut conditions = vault_ix::milestones(MAX_MILESTONES, TOTAL, &approver.pubkey());
+    conditions[2].deadline = Some(now + WEEK);
+    conditions[3].deadline = Some(now + WEEK);
+    let escrow = EscrowAccounts::new(&owner_program, escrows.len() as u64 + 7, &h.mint);
+    let create = vault_ix::create_escrow(
+        &escrow,
+        &depositor.pubkey(),
+        &beneficiary.pubkey(),
+        &Pubkey::new_unique(),
+        TOTAL,
+        ReleaseSchedule::Milestone { conditions },
+        ReleaseAuthority::Beneficiary,
+        None,
+    );
+    let deposit = vault_ix::deposit_funds(&escrow, &depositor.pubkey(), TOTAL);
+    h.process(&[create, deposit], &[&depositor]).await.unwrap();
+    let units = h.process(&[vault_ix::submit_milestone(&escrow, &beneficiary.pubkey(), 0, [1; 32])], &[&beneficiary]).await.unwrap();
+    bench.record("vault::submit_milestone", units);
+    let ix = vault_ix::review_milestone(&escrow, &approver.pubkey(), &beneficiary.pubkey(), 0, MilestoneReview::Approve);
+    let units = h.process(&[ix], &[&approver]).await.unwrap();
+    bench.record("vault::review_milestone", units);
+    h.process(&[vault_ix::submit_milestone(&escrow, &beneficiary.pubkey(), 1, [2; 32])], &[&beneficiary]).await.unwrap();
+    h.set_clock(now + WEEK / 2).await;
+    let units = h.process(&[vault_ix::finalize_milestone(&escrow, &beneficiary.pubkey(), 1)], &[]).await.unwrap();
+    bench.record("vault::finalize_milestone", units);
+    h.set_clock(now + 2 * WEEK).await;
+    let units = h.process(&[vault_ix::refund_missed_milestones(&escrow, &depositor.pubkey())], &[]).await.unwrap();
+    bench.record("vault::refund_missed_milestones", units);
+}
+#[tokio::test]
+#[ignore = "needs the SBF builds from `anchor build`"]
+async fn compute_units_within_baseline() {
+    let mut bench = Bench::default();
+    bench_stream_vault(&mut bench).await;
+    bench_vault(&mut bench).await;
+    bench.finish();
+}
+#[test]
+fn baseline_covers_every_instruction() {
+    let baseline = read_baseline(BASELINE_PATH);
+    let mut uncovered = Vec::new();
+    for (program, lib) in [
+        ("stream_vault", concat!(env!("CARGO_MANIFEST_DIR"), "/src/lib.rs")),
+        ("vault", concat!(env!("CARGO_MANIFEST_DIR"), "/../vault/src/lib.rs")),
+    ] {
+        let source = std::fs::read_to_string(lib).unwrap();
+        let (_, program_module) = source.split_once("#[program]").unwrap();
+        for line in program_module.lines() {
+            let Some(signature) = line.strip_prefix("    pub fn ") else { continue };
+            let name = format!("{program}::{}", &signature[..signature.find(['(', '<']).unwrap()]);
+            let variant = format!("{name}/");
+            if !baseline.keys().any(|entry| *entry == name || entry.starts_with(&variant)) {
+                uncovered.push(name);
+            }
+        }
+    }
+    assert!(uncovered.is_empty(), "no compute unit baseline for:\n{}", uncovered.join("\n"));
+}
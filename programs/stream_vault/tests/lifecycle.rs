@@ -0,0 +1,205 @@
+// End-to-end lifecycles against an in-process runtime, warping the clock to
+// exact fractions of the stream so every balance can be asserted exactly.
+mod common;
+use common::vault::{self as vault_ix, EscrowAccounts};
+use common::{program_error, Harness, Runtime, StreamAccounts, StreamParams, GENESIS_TIME, WEEK};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use stream_vault::errors::StreamVaultError;
+use stream_vault::strategies::AccelerationType;
+use stream_vault::vault_cpi::{LinearConfig, ReleaseAuthority, ReleaseSchedule, VaultAccelerationType};
+const TOTAL: u64 = 1_000_000_000;
+// Earned amounts at a quarter, half and three quarters of the stream.
+fn expected_earned(acceleration_type: AccelerationType) -> [u64; 3] {
+    match acceleration_type {
+        AccelerationType::Linear => [250_000_000, 500_000_000, 750_000_000],
+        AccelerationType::Quadratic => [62_500_000, 250_000_000, 562_500_000],
+        AccelerationType::Cubic => [15_625_000, 125_000_000, 421_875_000],
+    }
+}
+// create → fund → accrue → pause → resume → withdraw → close, with the
+// stream paused from half way through for a quarter of its duration.
+async fn run_lifecycle(runtime: Runtime, acceleration_type: AccelerationType, escrowed: bool) {
+    let mut h = Harness::start(runtime).await;
+    let employer = h.wallet(TOTAL).await;
+    let freelancer = h.wallet(0).await;
+    let stream = StreamAccounts::new(&employer.pubkey(), &freelancer.pubkey(), &h.mint, 1);
+    let employer_account = h.token_account(&employer.pubkey());
+    let freelancer_account = h.token_account(&freelancer.pubkey());
+    let [quarter, half, three_quarters] = expected_earned(acceleration_type);
+    let start = h.now().await + 60;
+    let params = StreamParams::new(start, TOTAL).accelerated(acceleration_type);
+    h.open_stream(&stream, &employer, params, escrowed).await.unwrap();
+    assert_eq!(h.token_balance(&employer_account).await, 0);
+    assert_eq!(held(&mut h, &stream).await, TOTAL);
+    assert_eq!(
+        h.process(&[stream.withdraw(&freelancer.pubkey(), None, escrowed)], &[&freelancer]).await,
+        Err(program_error(StreamVaultError::StreamNotStarted))
+    );
+
+    h.set_clock(start + WEEK / 4).await;
+    h.process(&[stream.withdraw(&freelancer.pubkey(), None, escrowed)], &[&freelancer]).await.unwrap();
+    assert_eq!(h.token_balance(&freelancer_account).await, quarter);
+    assert_eq!(held(&mut h, &stream).await, TOTAL - quarter);
+
+    h.set_clock(start + WEEK / 2).await;
+    h.process(&[stream.pause(&employer.pubkey())], &[&employer]).await.unwrap();
+    h.set_clock(start + 3 * WEEK / 4).await;
+    assert_eq!(
+        h.process(&[stream.withdraw(&freelancer.pubkey(), None, escrowed)], &[&freelancer]).await,
+        Err(program_error(StreamVaultError::StreamPaused))
+    );
+    h.process(&[stream.resume(&employer.pubkey())], &[&employer]).await.unwrap();
+    let state = h.stream(&stream.address).await;
+    assert_eq!(state.total_paused_duration, WEEK / 4);
+    assert_eq!(state.calculate_earned_amount(start + 3 * WEEK / 4).unwrap(), half);
+
+    // A quarter of the week was spent paused, so three quarters have accrued.
+    h.set_clock(start + WEEK).await;
+    h.process(&[stream.withdraw(&freelancer.pubkey(), None, escrowed)], &[&freelancer]).await.unwrap();
+    assert_eq!(h.token_balance(&freelancer_account).await, three_quarters);
+    assert_eq!(held(&mut h, &stream).await, TOTAL - three_quarters);
+    assert_eq!(
+        h.process(&[stream.close(&[freelancer_account], escrowed)], &[&employer]).await,
+        Err(program_error(StreamVaultError::StreamNotEnded))
+    );
+
+    h.set_clock(start + WEEK + WEEK / 4).await;
+    h.process(&[stream.close(&[freelancer_account], escrowed)], &[&employer]).await.unwrap();
+    assert_eq!(h.token_balance(&freelancer_account).await, TOTAL);
+    assert_eq!(h.token_balance(&employer_account).await, 0);
+    assert!(!h.account_exists(&stream.address).await);
+    assert!(!h.account_exists(&stream.vault).await);
+    if escrowed {
+        assert!(!h.account_exists(&stream.escrow().token_account).await);
+    }
+}
+// Tokens backing the stream, whether in its own vault or in vault custody.
+async fn held(h: &mut Harness, stream: &StreamAccounts) -> u64 {
+    h.token_balance(&stream.vault).await + h.token_balance(&stream.escrow().token_account).await
+}
+#[tokio::test]
+async fn linear_stream_lifecycle() {
+    run_lifecycle(Runtime::Native, AccelerationType::Linear, false).await;
+}
+#[tokio::test]
+async fn quadratic_stream_lifecycle() {
+    run_lifecycle(Runtime::Native, AccelerationType::Quadratic, false).await;
+}
+#[tokio::test]
+async fn cubic_stream_lifecycle() {
+    run_lifecycle(Runtime::Native, AccelerationType::Cubic, false).await;
+}
+#[tokio::test]
+#[ignore = "needs the SBF builds from `anchor build`"]
+async fn escrowed_stream_lifecycle() {
+    for acceleration_type in [AccelerationType::Linear, AccelerationType::Quadratic, AccelerationType::Cubic] {
+        run_lifecycle(Runtime::Sbf, acceleration_type, true).await;
+    }
+}
+struct EscrowCase {
+    h: Harness,
+    escrow: EscrowAccounts,
+    depositor: Keypair,
+    beneficiary: Keypair,
+    approver: Keypair,
+}
+impl EscrowCase {
+    async fn open(schedule: impl FnOnce(i64, &Pubkey) -> ReleaseSchedule) -> Self {
+        let mut h = Harness::start(Runtime::Sbf).await;
+        let depositor = h.wallet(TOTAL).await;
+        let beneficiary = h.wallet(0).await;
+        let approver = Keypair::new();
+        let start = h.now().await;
+        let escrow = EscrowAccounts::new(&Pubkey::new_unique(), 0, &h.mint);
+        h.process(
+            &[
+                vault_ix::create_escrow(
+                    &escrow,
+                    &depositor.pubkey(),
+                    &beneficiary.pubkey(),
+                    &Pubkey::new_unique(),
+                    TOTAL,
+                    schedule(start, &approver.pubkey()),
+                    ReleaseAuthority::Beneficiary,
+                    None,
+                ),
+                vault_ix::deposit_funds(&escrow, &depositor.pubkey(), TOTAL),
+            ],
+            &[&depositor],
+        ).await.unwrap();
+        assert_eq!(h.token_balance(&escrow.token_account).await, TOTAL);
+        Self { h, escrow, depositor, beneficiary, approver }
+    }
+    async fn release(&mut self, milestone_id: u32) {
+        let ix = vault_ix::release_milestone(&self.escrow, &self.approver.pubkey(), milestone_id);
+        self.h.process(&[ix], &[&self.approver]).await.unwrap();
+    }
+    // Withdraws everything available at `time` and returns the beneficiary's balance.
+    async fn withdraw_at(&mut self, time: i64) -> u64 {
+        self.h.set_clock(time).await;
+        let ix = vault_ix::withdraw_available(&self.escrow, &self.beneficiary.pubkey(), None);
+        self.h.process(&[ix], &[&self.beneficiary]).await.unwrap();
+        let balance = self.h.token_balance(&self.h.token_account(&self.beneficiary.pubkey())).await;
+        assert_eq!(self.h.token_balance(&self.escrow.token_account).await, TOTAL - balance);
+        balance
+    }
+    async fn nothing_available_at(&mut self, time: i64) -> bool {
+        self.h.set_clock(time).await;
+        let ix = vault_ix::withdraw_available(&self.escrow, &self.beneficiary.pubkey(), None);
+        self.h.process(&[ix], &[&self.beneficiary]).await.is_err()
+    }
+}
+#[tokio::test]
+#[ignore = "needs the SBF builds from `anchor build`"]
+async fn vault_release_schedules() {
+    // Every case starts from a fresh runtime at the same genesis time.
+    let start = GENESIS_TIME;
+    let mut case = EscrowCase::open(|_, _| ReleaseSchedule::Immediate).await;
+    assert_eq!(case.withdraw_at(start + 1).await, TOTAL);
+
+    let mut case = EscrowCase::open(|start, _| ReleaseSchedule::Linear { start, end: start + WEEK }).await;
+    assert_eq!(case.withdraw_at(start + WEEK / 4).await, TOTAL / 4);
+    assert_eq!(case.withdraw_at(start + 3 * WEEK / 4).await, 3 * TOTAL / 4);
+    assert_eq!(case.withdraw_at(start + 2 * WEEK).await, TOTAL);
+
+    let mut case = EscrowCase::open(|_, approver| ReleaseSchedule::Milestone {
+        conditions: vault_ix::milestones(4, TOTAL, approver),
+    }).await;
+    assert!(case.nothing_available_at(start + 1).await);
+    case.release(1).await;
+    assert_eq!(case.withdraw_at(start + 2).await, TOTAL / 4);
+    case.release(0).await;
+    case.release(2).await;
+    assert_eq!(case.withdraw_at(start + 3).await, 3 * TOTAL / 4);
+    case.release(3).await;
+    assert_eq!(case.withdraw_at(start + 4).await, TOTAL);
+
+    // Half streams quadratically over the week, half is split over two milestones.
+    let mut case = EscrowCase::open(|start, approver| ReleaseSchedule::Hybrid {
+        linear_portion: TOTAL / 2,
+        milestone_portion: TOTAL / 2,
+        linear_config: LinearConfig {
+            start_time: start,
+            end_time: start + WEEK,
+            acceleration_type: VaultAccelerationType::Quadratic,
+        },
+        milestone_config: vault_ix::milestones(2, TOTAL / 2, approver),
+    }).await;
+    assert_eq!(case.withdraw_at(start + WEEK / 2).await, TOTAL / 8);
+    case.release(0).await;
+    assert_eq!(case.withdraw_at(start + WEEK / 2 + 1).await, TOTAL / 8 + TOTAL / 4);
+    assert_eq!(case.withdraw_at(start + WEEK).await, TOTAL / 2 + TOTAL / 4);
+    case.release(1).await;
+    assert_eq!(case.withdraw_at(start + WEEK + 1).await, TOTAL);
+
+    // Custom schedules cannot be evaluated on-chain yet; the deposit stays
+    // locked until the depositor closes the escrow.
+    let mut case = EscrowCase::open(|_, _| ReleaseSchedule::Custom { data: vec![1, 2, 3] }).await;
+    assert!(case.nothing_available_at(start + 2 * WEEK).await);
+    let depositor = case.depositor.pubkey();
+    let ix = vault_ix::close_escrow(&case.escrow, &depositor, &depositor);
+    case.h.process(&[ix], &[&case.depositor]).await.unwrap();
+    assert_eq!(case.h.token_balance(&case.h.token_account(&depositor)).await, TOTAL);
+    assert!(!case.h.account_exists(&case.escrow.token_account).await);
+}
@@ -0,0 +1,253 @@
+// Stateful model-based fuzzing of the stream lifecycle. Random sequences of
+// operations run against the real program in an in-process runtime and every
+// step is checked against a small reference model of the stream.
+mod common;
+use common::{Harness, Runtime, StreamAccounts, StreamParams, WEEK};
+use std::cell::RefCell;
+use proptest::prelude::*;
+use proptest::test_runner::TestRunner;
+use solana_sdk::signature::{Keypair, Signer};
+use stream_vault::constants::{MAX_PAUSE_RESUME_COUNT, MIN_WITHDRAWAL_AMOUNT_USDC};
+use stream_vault::strategies::AccelerationType;
+const EMPLOYER_FUNDS: u64 = 1_000_000_000_000_000;
+const DAY: i64 = 24 * 60 * 60;
+#[derive(Clone, Debug)]
+enum Op {
+    Create { start_delay: i64, total_amount: u64, acceleration_type: AccelerationType },
+    Deposit,
+    Pause,
+    Resume,
+    // Withdraw a percentage of what is withdrawable, or all of it.
+    Withdraw { percent: Option<u64> },
+    Close,
+    Advance { seconds: i64 },
+}
+fn acceleration_type() -> impl Strategy<Value = AccelerationType> {
+    prop_oneof![
+        Just(AccelerationType::Linear),
+        Just(AccelerationType::Quadratic),
+        Just(AccelerationType::Cubic),
+    ]
+}
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        1 => (0..DAY, 100_000_000u64..=100_000_000_000, acceleration_type()).prop_map(
+            |(start_delay, total_amount, acceleration_type)| Op::Create { start_delay, total_amount, acceleration_type }
+        ),
+        1 => Just(Op::Deposit),
+        1 => Just(Op::Pause),
+        1 => Just(Op::Resume),
+        2 => prop::option::of(1u64..=100).prop_map(|percent| Op::Withdraw { percent }),
+        1 => Just(Op::Close),
+        3 => prop_oneof![0..DAY, DAY..WEEK, WEEK..3 * WEEK].prop_map(|seconds| Op::Advance { seconds }),
+    ]
+}
+// What the stream should look like, tracked independently of the program.
+struct ModelStream {
+    accounts: StreamAccounts,
+    start: i64,
+    end: i64,
+    total: u64,
+    acceleration_type: AccelerationType,
+    funded: bool,
+    pause_time: Option<i64>,
+    paused_total: i64,
+    pause_resume_count: u8,
+    withdrawn: u64,
+}
+impl ModelStream {
+    // Earnings follow `total * progress^k`, where progress excludes time spent
+    // paused and a pause freezes it until the stream is resumed.
+    fn earned(&self, now: i64) -> u64 {
+        if now < self.start {
+            return 0;
+        }
+        let effective_now = self.pause_time.unwrap_or(now);
+        if effective_now >= self.end + self.paused_total {
+            return self.total;
+        }
+        let elapsed = (effective_now - self.start - self.paused_total).max(0);
+        let progress = elapsed as f64 / (self.end - self.start) as f64;
+        let exponent = self.acceleration_type.to_factor();
+        ((self.total as f64 * progress.powf(exponent)) as u64).min(self.total)
+    }
+    fn withdrawable(&self, now: i64) -> u64 {
+        self.earned(now).saturating_sub(self.withdrawn)
+    }
+    fn is_ended(&self, now: i64) -> bool {
+        now >= self.end + self.paused_total
+    }
+}
+struct Model {
+    stream: Option<ModelStream>,
+    next_stream_id: u64,
+    deposited: u64,
+    paid: u64,
+    refunded: u64,
+}
+struct Fixture {
+    h: Harness,
+    employer: Keypair,
+    freelancer: Keypair,
+}
+impl Fixture {
+    async fn step(&mut self, model: &mut Model, op: &Op) -> Result<(), TestCaseError> {
+        let now = self.h.now().await;
+        let employer = &self.employer;
+        let freelancer = &self.freelancer;
+        match (op, model.stream.as_mut()) {
+            (Op::Advance { seconds }, _) => {
+                self.h.advance_clock(*seconds).await;
+                return Ok(());
+            },
+            (Op::Create { start_delay, total_amount, acceleration_type }, None) => {
+                let accounts = StreamAccounts::new(&employer.pubkey(), &freelancer.pubkey(), &self.h.mint, model.next_stream_id);
+                let params = StreamParams::new(now + start_delay, *total_amount).accelerated(*acceleration_type);
+                let result = self.h.process(&[accounts.create(params, false)], &[employer]).await;
+                prop_assert!(result.is_ok(), "create failed: {:?}", result);
+                model.next_stream_id += 1;
+                model.stream = Some(ModelStream {
+                    accounts,
+                    start: now + start_delay,
+                    end: now + start_delay + WEEK,
+                    total: *total_amount,
+                    acceleration_type: *acceleration_type,
+                    funded: false,
+                    pause_time: None,
+                    paused_total: 0,
+                    pause_resume_count: 0,
+                    withdrawn: 0,
+                });
+            },
+            (Op::Deposit, Some(stream)) => {
+                let allowed = !stream.funded && now < stream.start;
+                let result = self.h.process(&[stream.accounts.deposit(stream.total, false)], &[employer]).await;
+                prop_assert_eq!(result.is_ok(), allowed, "deposit: {:?}", result);
+                if allowed {
+                    stream.funded = true;
+                    model.deposited += stream.total;
+                }
+            },
+            (Op::Pause, Some(stream)) => {
+                let allowed = stream.funded && stream.pause_time.is_none() && stream.pause_resume_count < MAX_PAUSE_RESUME_COUNT;
+                let result = self.h.process(&[stream.accounts.pause(&employer.pubkey())], &[employer]).await;
+                prop_assert_eq!(result.is_ok(), allowed, "pause: {:?}", result);
+                if allowed {
+                    stream.pause_time = Some(now);
+                    stream.pause_resume_count += 1;
+                }
+            },
+            (Op::Resume, Some(stream)) => {
+                // A stream paused for longer than its own duration can no longer be resumed.
+                let allowed = stream.funded &&
+                    stream.pause_time.is_some_and(|pause_time| now - pause_time <= stream.end - stream.start) &&
+                    stream.pause_resume_count < MAX_PAUSE_RESUME_COUNT;
+                let result = self.h.process(&[stream.accounts.resume(&employer.pubkey())], &[employer]).await;
+                prop_assert_eq!(result.is_ok(), allowed, "resume: {:?}", result);
+                if allowed {
+                    stream.paused_total += now - stream.pause_time.take().unwrap();
+                    stream.pause_resume_count += 1;
+                }
+            },
+            (Op::Withdraw { percent }, Some(stream)) => {
+                let withdrawable = stream.withdrawable(now);
+                let amount = percent.map(|percent| withdrawable * percent / 100);
+                let payout = amount.unwrap_or(withdrawable);
+                let allowed = stream.funded &&
+                    stream.pause_time.is_none() &&
+                    now >= stream.start &&
+                    stream.withdrawn < stream.total &&
+                    payout > 0 &&
+                    payout >= MIN_WITHDRAWAL_AMOUNT_USDC;
+                let ix = stream.accounts.withdraw(&freelancer.pubkey(), amount.filter(|amount| *amount > 0), false);
+                let result = self.h.process(&[ix], &[freelancer]).await;
+                prop_assert_eq!(result.is_ok(), allowed, "withdraw {:?} of {}: {:?}", amount, withdrawable, result);
+                if allowed {
+                    stream.withdrawn += payout;
+                    model.paid += payout;
+                }
+            },
+            (Op::Close, Some(stream)) => {
+                let allowed = stream.is_ended(now) || stream.withdrawn >= stream.total;
+                let freelancer_account = self.h.token_account(&freelancer.pubkey());
+                let result = self.h.process(&[stream.accounts.close(&[freelancer_account], false)], &[employer]).await;
+                prop_assert_eq!(result.is_ok(), allowed, "close: {:?}", result);
+                if allowed {
+                    let held = if stream.funded { stream.total - stream.withdrawn } else { 0 };
+                    let payout = if stream.funded { stream.withdrawable(now).min(held) } else { 0 };
+                    model.paid += payout;
+                    model.refunded += held - payout;
+                    prop_assert!(!self.h.account_exists(&stream.accounts.address).await);
+                    prop_assert!(!self.h.account_exists(&stream.accounts.vault).await);
+                    model.stream = None;
+                }
+            },
+            // Operations on a stream that does not exist, or creating a second one, are skipped.
+            _ => return Ok(()),
+        }
+        self.check(model).await
+    }
+    async fn check(&mut self, model: &Model) -> Result<(), TestCaseError> {
+        let now = self.h.now().await;
+        let employer_balance = self.h.token_balance(&self.h.token_account(&self.employer.pubkey())).await;
+        let freelancer_balance = self.h.token_balance(&self.h.token_account(&self.freelancer.pubkey())).await;
+        prop_assert_eq!(freelancer_balance, model.paid);
+        prop_assert_eq!(employer_balance, EMPLOYER_FUNDS - model.deposited + model.refunded);
+        let vault_balance = match &model.stream {
+            Some(stream) => {
+                let state = self.h.stream(&stream.accounts.address).await;
+                prop_assert_eq!(state.is_funded(), stream.funded);
+                prop_assert_eq!(state.pause_time(), stream.pause_time);
+                prop_assert_eq!(state.total_paused_duration, stream.paused_total);
+                prop_assert_eq!(state.withdrawn_amount, stream.withdrawn);
+                // Nothing is ever paid out before it has been earned.
+                prop_assert!(stream.withdrawn <= stream.earned(now));
+                prop_assert!(state.withdrawn_amount <= state.calculate_earned_amount(now).unwrap());
+                self.h.token_balance(&stream.accounts.vault).await
+            },
+            None => 0,
+        };
+        // Every deposited token is either still in the vault or has been paid or refunded.
+        prop_assert_eq!(vault_balance + model.paid + model.refunded, model.deposited);
+        prop_assert_eq!(employer_balance + freelancer_balance + vault_balance, EMPLOYER_FUNDS);
+        Ok(())
+    }
+}
+// Each case runs against fresh wallets on a runtime shared by all cases, as
+// starting a new runtime per case dominates the run time.
+async fn run(h: Harness, ops: &[Op]) -> (Harness, Result<(), TestCaseError>) {
+    let mut h = h;
+    let employer = h.wallet(EMPLOYER_FUNDS).await;
+    let freelancer = h.wallet(0).await;
+    let mut fixture = Fixture { h, employer, freelancer };
+    let mut model = Model {
+        stream: None,
+        next_stream_id: 0,
+        deposited: 0,
+        paid: 0,
+        refunded: 0,
+    };
+    for op in ops {
+        // Every operation lands in a fresh slot, as consecutive transactions would.
+        fixture.h.advance_clock(0).await;
+        if let Err(error) = fixture.step(&mut model, op).await {
+            return (fixture.h, Err(error));
+        }
+    }
+    (fixture.h, Ok(()))
+}
+#[test]
+fn stream_lifecycle_matches_model() {
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+    let harness = RefCell::new(Some(runtime.block_on(Harness::start(Runtime::Native))));
+    let mut runner = TestRunner::new(ProptestConfig::with_cases(64));
+    let result = runner.run(&prop::collection::vec(op(), 1..40), |ops| {
+        let h = harness.borrow_mut().take().unwrap();
+        let (h, result) = runtime.block_on(run(h, &ops));
+        *harness.borrow_mut() = Some(h);
+        result
+    });
+    if let Err(error) = result {
+        panic!("{error}");
+    }
+}
@@ -0,0 +1,105 @@
+// Multisig governance: the employer acts only through proposals, executed
+// proposals return their rent to the proposer, signers rotate through
+// proposals, and a stream manager keeps its direct pause/resume rights.
+mod common;
+use common::{program_error, Harness, Runtime, StreamAccounts, StreamParams, WEEK};
+use solana_sdk::signature::{Keypair, Signer};
+use stream_vault::errors::StreamVaultError;
+use stream_vault::state::StreamAction;
+const TOTAL: u64 = 1_000_000_000;
+struct Governed {
+    h: Harness,
+    stream: StreamAccounts,
+    employer: Keypair,
+    cosigner: Keypair,
+    manager: Keypair,
+    start: i64,
+}
+async fn governed() -> Governed {
+    let mut h = Harness::start(Runtime::Native).await;
+    let employer = h.wallet(TOTAL).await;
+    let cosigner = h.wallet(0).await;
+    let manager = h.wallet(0).await;
+    let stream = StreamAccounts::new(&employer.pubkey(), &Keypair::new().pubkey(), &h.mint, 1);
+    let start = h.now().await + 60;
+    h.open_stream(&stream, &employer, StreamParams::new(start, TOTAL), false).await.unwrap();
+    h.process(&[stream.set_manager(Some(manager.pubkey()))], &[&employer]).await.unwrap();
+    let signers = vec![employer.pubkey(), cosigner.pubkey()];
+    h.process(&[stream.configure_multisig(signers, 2)], &[&employer]).await.unwrap();
+    Governed { h, stream, employer, cosigner, manager, start }
+}
+// Proposes `action` from the employer, approves it with the cosigner and executes it.
+async fn pass(g: &mut Governed, proposal_id: u64, action: StreamAction) {
+    let employer = g.employer.pubkey();
+    g.h.process(&[g.stream.create_proposal(&employer, proposal_id, action, WEEK)], &[&g.employer]).await.unwrap();
+    g.h.process(&[g.stream.approve_proposal(&g.cosigner.pubkey(), proposal_id)], &[&g.cosigner]).await.unwrap();
+    g.h.process(&[g.stream.execute_proposal(&employer, &employer, proposal_id, &[])], &[&g.employer]).await.unwrap();
+}
+#[tokio::test]
+async fn manager_pauses_directly_under_multisig() {
+    let mut g = governed().await;
+    let (stream, employer, manager) = (g.stream.address, g.employer.insecure_clone(), g.manager.insecure_clone());
+    g.h.set_clock(g.start + WEEK / 4).await;
+    assert_eq!(
+        g.h.process(&[g.stream.pause(&employer.pubkey())], &[&employer]).await,
+        Err(program_error(StreamVaultError::MultisigRequired))
+    );
+    assert_eq!(
+        g.h.process(&[g.stream.set_manager(None)], &[&employer]).await,
+        Err(program_error(StreamVaultError::MultisigRequired))
+    );
+    g.h.process(&[g.stream.pause(&manager.pubkey())], &[&manager]).await.unwrap();
+    assert!(g.h.stream(&stream).await.is_paused());
+    g.h.set_clock(g.start + WEEK / 2).await;
+    g.h.process(&[g.stream.resume(&manager.pubkey())], &[&manager]).await.unwrap();
+    assert!(!g.h.stream(&stream).await.is_paused());
+
+    // Removing the manager takes a proposal, after which it can no longer pause.
+    pass(&mut g, 0, StreamAction::SetManager { manager: None }).await;
+    assert_eq!(g.h.stream(&stream).await.manager(), None);
+    g.h.set_clock(g.start + 3 * WEEK / 4).await;
+    assert_eq!(
+        g.h.process(&[g.stream.pause(&manager.pubkey())], &[&manager]).await,
+        Err(program_error(StreamVaultError::OnlyEmployerCanPauseResume))
+    );
+}
+#[tokio::test]
+async fn executed_proposals_close_to_proposer() {
+    let mut g = governed().await;
+    g.h.set_clock(g.start + WEEK / 4).await;
+    let employer = g.employer.pubkey();
+    let proposal = g.stream.proposal(0);
+    g.h.process(&[g.stream.create_proposal(&employer, 0, StreamAction::Pause, WEEK)], &[&g.employer]).await.unwrap();
+    g.h.process(&[g.stream.approve_proposal(&g.cosigner.pubkey(), 0)], &[&g.cosigner]).await.unwrap();
+    let rent = g.h.context.banks_client.get_balance(proposal).await.unwrap();
+    let before = g.h.context.banks_client.get_balance(employer).await.unwrap();
+    // The cosigner executes, so the employer's balance only moves by the refunded rent.
+    let cosigner = g.cosigner.insecure_clone();
+    g.h.process(&[g.stream.execute_proposal(&cosigner.pubkey(), &employer, 0, &[])], &[&cosigner]).await.unwrap();
+    assert!(!g.h.account_exists(&proposal).await);
+    assert_eq!(g.h.context.banks_client.get_balance(employer).await.unwrap(), before + rent);
+    assert!(g.h.stream(&g.stream.address).await.is_paused());
+}
+#[tokio::test]
+async fn signers_rotate_through_proposals() {
+    let mut g = governed().await;
+    let replacement = g.h.wallet(0).await;
+    pass(&mut g, 0, StreamAction::AddSigner { signer: replacement.pubkey() }).await;
+    let removed = g.cosigner.pubkey();
+    pass(&mut g, 1, StreamAction::RemoveSigner { signer: removed }).await;
+    let multisig = g.h.multisig(&g.stream.multisig()).await;
+    assert_eq!(multisig.signers, vec![g.employer.pubkey(), replacement.pubkey()]);
+    assert_eq!(multisig.threshold, 2);
+
+    // The removed cosigner can no longer approve; the replacement can.
+    let employer = g.employer.pubkey();
+    g.h.process(&[g.stream.create_proposal(&employer, 2, StreamAction::ChangeThreshold { threshold: 1 }, WEEK)], &[&g.employer]).await.unwrap();
+    let cosigner = g.cosigner.insecure_clone();
+    assert_eq!(
+        g.h.process(&[g.stream.approve_proposal(&cosigner.pubkey(), 2)], &[&cosigner]).await,
+        Err(program_error(StreamVaultError::Unauthorized))
+    );
+    g.h.process(&[g.stream.approve_proposal(&replacement.pubkey(), 2)], &[&replacement]).await.unwrap();
+    g.h.process(&[g.stream.execute_proposal(&employer, &employer, 2, &[])], &[&g.employer]).await.unwrap();
+    assert_eq!(g.h.multisig(&g.stream.multisig()).await.threshold, 1);
+}
@@ -0,0 +1,107 @@
+// Past start times under each start-time policy, and the slot/time guard
+// capping accrual when the cluster clock runs ahead of the slots produced.
+mod common;
+use common::{program_error, update_stream_config, Harness, Runtime, StreamAccounts, StreamParams, WEEK};
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::sysvar::epoch_schedule::EpochSchedule;
+use stream_vault::constants::NOMINAL_SLOT_DURATION_MS;
+use stream_vault::errors::StreamVaultError;
+use stream_vault::state::{FeeMode, StartTimePolicy};
+const TOTAL: u64 = 1_000_000_000;
+async fn start(policy: StartTimePolicy, max_slot_duration_ms: u32) -> (Harness, Keypair, Keypair) {
+    let mut h = Harness::start(Runtime::Native).await;
+    let payer = h.payer();
+    let fee_recipient = h.fee_recipient;
+    h.process(
+        &[update_stream_config(&payer.pubkey(), &fee_recipient, 0, FeeMode::OnWithdrawal, policy, max_slot_duration_ms)],
+        &[],
+    ).await.unwrap();
+    let employer = h.wallet(TOTAL).await;
+    let freelancer = h.wallet(0).await;
+    (h, employer, freelancer)
+}
+async fn withdrawn(h: &mut Harness, stream: &StreamAccounts, freelancer: &Keypair) -> u64 {
+    h.process(&[stream.withdraw(&freelancer.pubkey(), None, false)], &[freelancer]).await.unwrap();
+    h.token_balance(&h.token_account(&freelancer.pubkey())).await
+}
+#[tokio::test]
+async fn strict_policy_rejects_past_start() {
+    let (mut h, employer, _) = start(StartTimePolicy::Strict, 0).await;
+    let stream = StreamAccounts::new(&employer.pubkey(), &Keypair::new().pubkey(), &h.mint, 1);
+    let now = h.now().await;
+    assert_eq!(
+        h.process(&[stream.create(StreamParams::new(now - 1, TOTAL), false)], &[&employer]).await,
+        Err(program_error(StreamVaultError::StartTimeInPast))
+    );
+    h.process(&[stream.create(StreamParams::new(now, TOTAL), false)], &[&employer]).await.unwrap();
+    let payer = h.payer();
+    let fee_recipient = h.fee_recipient;
+    let policy = StartTimePolicy::Backdate { max_backdate: -1 };
+    assert_eq!(
+        h.process(&[update_stream_config(&payer.pubkey(), &fee_recipient, 0, FeeMode::OnWithdrawal, policy, 0)], &[]).await,
+        Err(program_error(StreamVaultError::InvalidStartTimePolicy))
+    );
+}
+#[tokio::test]
+async fn backdated_stream_accrues_from_funding() {
+    let (mut h, employer, freelancer) = start(StartTimePolicy::Backdate { max_backdate: WEEK / 2 }, 0).await;
+    let stream = StreamAccounts::new(&employer.pubkey(), &freelancer.pubkey(), &h.mint, 1);
+    let now = h.now().await;
+    assert_eq!(
+        h.process(&[stream.create(StreamParams::new(now - WEEK / 2 - 1, TOTAL), false)], &[&employer]).await,
+        Err(program_error(StreamVaultError::StartTimeInPast))
+    );
+    h.process(&[stream.create(StreamParams::new(now - WEEK / 4, TOTAL), false)], &[&employer]).await.unwrap();
+    let funded_at = now + 60;
+    h.set_clock(funded_at).await;
+    h.process(&[stream.deposit(TOTAL, false)], &[&employer]).await.unwrap();
+    let state = h.stream(&stream.address).await;
+    assert_eq!((state.start_time, state.end_time), (funded_at, funded_at + WEEK));
+
+    h.set_clock(funded_at + WEEK / 4).await;
+    assert_eq!(withdrawn(&mut h, &stream, &freelancer).await, TOTAL / 4);
+}
+#[tokio::test]
+async fn catch_up_pays_accrued_amount_on_funding() {
+    let (mut h, employer, freelancer) = start(StartTimePolicy::BackdateWithCatchUp { max_backdate: WEEK / 2 }, 0).await;
+    let stream = StreamAccounts::new(&employer.pubkey(), &freelancer.pubkey(), &h.mint, 1);
+    let now = h.now().await;
+    h.process(&[stream.create(StreamParams::new(now - WEEK / 4, TOTAL), false)], &[&employer]).await.unwrap();
+    h.set_clock(now).await;
+    h.process(&[stream.deposit(TOTAL, false)], &[&employer]).await.unwrap();
+    assert_eq!(h.stream(&stream.address).await.start_time, now - WEEK / 4);
+
+    h.set_clock(now).await;
+    assert_eq!(withdrawn(&mut h, &stream, &freelancer).await, TOTAL / 4);
+}
+#[tokio::test]
+async fn slot_guard_caps_accrual_when_clock_runs_ahead() {
+    let (mut h, employer, freelancer) = start(StartTimePolicy::Strict, 1000).await;
+    let stream = StreamAccounts::new(&employer.pubkey(), &freelancer.pubkey(), &h.mint, 1);
+    let start = h.now().await + 60;
+    h.open_stream(&stream, &employer, StreamParams::new(start, TOTAL), false).await.unwrap();
+
+    // The cluster reports half the week as elapsed, but the slots produced
+    // since the epoch started only account for a quarter of it.
+    let epoch_schedule = h.context.banks_client.get_sysvar::<EpochSchedule>().await.unwrap();
+    h.update_clock(|clock| {
+        let elapsed_slots = (clock.slot - epoch_schedule.get_first_slot_in_epoch(clock.epoch)) as i64;
+        clock.unix_timestamp = start + WEEK / 2;
+        clock.epoch_start_timestamp = start + WEEK / 4 - elapsed_slots;
+    }).await;
+    assert_eq!(withdrawn(&mut h, &stream, &freelancer).await, TOTAL / 4);
+}
+#[tokio::test]
+async fn slot_guard_must_cover_a_slot() {
+    let (mut h, _, _) = start(StartTimePolicy::Strict, 0).await;
+    let payer = h.payer();
+    let fee_recipient = h.fee_recipient;
+    let update = |max_slot_duration_ms| {
+        update_stream_config(&payer.pubkey(), &fee_recipient, 0, FeeMode::OnWithdrawal, StartTimePolicy::Strict, max_slot_duration_ms)
+    };
+    assert_eq!(
+        h.process(&[update(NOMINAL_SLOT_DURATION_MS - 1)], &[]).await,
+        Err(program_error(StreamVaultError::InvalidSlotDuration))
+    );
+    h.process(&[update(NOMINAL_SLOT_DURATION_MS)], &[]).await.unwrap();
+}
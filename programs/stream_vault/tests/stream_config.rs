@@ -0,0 +1,67 @@
+// Who may create the stream config, and withdrawal quotes for tokenized
+// receivables.
+mod common;
+use common::{initialize_stream_config, program_error, Harness, Runtime, StreamAccounts, StreamParams, WEEK};
+use anchor_lang::AnchorDeserialize;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use stream_vault::errors::StreamVaultError;
+use stream_vault::state::{FeeMode, WithdrawalQuote};
+const TOTAL: u64 = 1_000_000_000;
+#[tokio::test]
+async fn only_upgrade_authority_initializes_config() {
+    let mut h = Harness::new(Runtime::Native).await;
+    let impostor = h.wallet(0).await;
+    let fee_recipient = h.fee_recipient;
+    assert_eq!(
+        h.process(&[initialize_stream_config(&impostor.pubkey(), &fee_recipient, 0, FeeMode::OnWithdrawal)], &[&impostor]).await,
+        Err(program_error(StreamVaultError::Unauthorized))
+    );
+    h.set_upgrade_authority(None);
+    let payer = h.payer();
+    assert_eq!(
+        h.process(&[initialize_stream_config(&payer.pubkey(), &fee_recipient, 0, FeeMode::OnWithdrawal)], &[]).await,
+        Err(program_error(StreamVaultError::Unauthorized))
+    );
+    h.set_upgrade_authority(Some(impostor.pubkey()));
+    // Same transaction as the first attempt, so it needs a fresh blockhash.
+    h.advance_clock(1).await;
+    h.process(&[initialize_stream_config(&impostor.pubkey(), &fee_recipient, 0, FeeMode::OnWithdrawal)], &[&impostor]).await.unwrap();
+}
+async fn quote(h: &mut Harness, stream: &StreamAccounts, recipient: &Pubkey) -> Result<WithdrawalQuote, solana_sdk::transaction::TransactionError> {
+    let (result, return_data) = h.simulate(&[stream.quote_withdrawal(recipient, true)], &[]).await;
+    result.map(|_| WithdrawalQuote::deserialize(&mut &return_data.unwrap()[..]).unwrap())
+}
+#[tokio::test]
+async fn quotes_follow_the_receivable_holder() {
+    let mut h = Harness::start(Runtime::Native).await;
+    let employer = h.wallet(TOTAL).await;
+    let freelancer = h.wallet(0).await;
+    let buyer = h.wallet(0).await;
+    let stream = StreamAccounts::new(&employer.pubkey(), &freelancer.pubkey(), &h.mint, 1);
+    let start = h.now().await + 60;
+    h.open_stream(&stream, &employer, StreamParams::new(start, TOTAL), false).await.unwrap();
+    h.process(&[stream.mint_receivable()], &[&employer]).await.unwrap();
+
+    let receivable_mint = stream.receivable_mint();
+    let buyer_receivable = get_associated_token_address(&buyer.pubkey(), &receivable_mint);
+    h.set_token_account_with_mint(buyer_receivable, &receivable_mint, &buyer.pubkey(), 0);
+    let transfer = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &get_associated_token_address(&freelancer.pubkey(), &receivable_mint),
+        &buyer_receivable,
+        &freelancer.pubkey(),
+        &[],
+        1,
+    ).unwrap();
+    h.process(&[transfer], &[&freelancer]).await.unwrap();
+
+    h.set_clock(start + WEEK / 4).await;
+    assert_eq!(quote(&mut h, &stream, &buyer.pubkey()).await.unwrap().gross_amount, TOTAL / 4);
+    assert_eq!(
+        quote(&mut h, &stream, &freelancer.pubkey()).await,
+        Err(program_error(StreamVaultError::NotReceivableHolder))
+    );
+}
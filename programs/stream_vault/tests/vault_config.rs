@@ -0,0 +1,72 @@
+// Administration of the vault program's `EscrowConfig`: updates, the
+// emergency pause and the two-step authority handover.
+mod common;
+use common::vault::{self as vault_ix, ConfigParams, EscrowAccounts};
+use common::{Harness, Runtime, WEEK};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use stream_vault::vault_cpi::{ReleaseAuthority, ReleaseSchedule, VaultExpiryPolicy, VaultFeeMode};
+const TOTAL: u64 = 1_000_000_000;
+// Config layout: discriminator, layout version, authority, pending authority,
+// fee, fee recipient, fee mode, limits, expiry policy, grace period, review
+// window, paused, version and bump.
+const PAUSED_OFFSET: usize = 8 + 1 + 32 + 33 + 2 + 32 + 1 + 8 + 8 + 1 + 8 + 8;
+async fn config(h: &mut Harness) -> (Pubkey, bool, u32) {
+    let data = h.account_data(&vault_ix::config_address()).await;
+    let authority = Pubkey::try_from(&data[9..41]).unwrap();
+    let version = u32::from_le_bytes(data[PAUSED_OFFSET + 1..PAUSED_OFFSET + 5].try_into().unwrap());
+    (authority, data[PAUSED_OFFSET] == 1, version)
+}
+#[tokio::test]
+#[ignore = "needs the SBF builds from `anchor build`"]
+async fn vault_config_administration() {
+    let mut h = Harness::start(Runtime::Sbf).await;
+    let admin = h.payer();
+    let depositor = h.wallet(TOTAL).await;
+    let outsider = Keypair::new();
+    let fee_recipient = h.fee_recipient;
+    assert_eq!(config(&mut h).await, (admin.pubkey(), false, 1));
+    let update = |authority: &Keypair| {
+        let params = ConfigParams {
+            fee_basis_points: 50,
+            fee_mode: VaultFeeMode::Inclusive,
+            min_escrow_amount: 10,
+            max_escrow_duration: WEEK,
+            expiry_policy: VaultExpiryPolicy::PayVested,
+            ..Default::default()
+        };
+        vault_ix::update_config(&authority.pubkey(), &fee_recipient, params)
+    };
+    assert!(h.process(&[update(&outsider)], &[&outsider]).await.is_err());
+    h.process(&[update(&admin)], &[]).await.unwrap();
+    assert_eq!(config(&mut h).await.2, 2);
+
+    // Nothing new can be escrowed while the program is paused.
+    h.process(&[vault_ix::set_config_paused(&admin.pubkey(), true)], &[]).await.unwrap();
+    assert_eq!(config(&mut h).await, (admin.pubkey(), true, 3));
+    let escrow = EscrowAccounts::new(&Pubkey::new_unique(), 0, &h.mint);
+    let create = vault_ix::create_escrow(
+        &escrow,
+        &depositor.pubkey(),
+        &Pubkey::new_unique(),
+        &Pubkey::new_unique(),
+        TOTAL,
+        ReleaseSchedule::Immediate,
+        ReleaseAuthority::Beneficiary,
+        None,
+    );
+    assert!(h.process(std::slice::from_ref(&create), &[&depositor]).await.is_err());
+    h.process(&[vault_ix::set_config_paused(&admin.pubkey(), false)], &[]).await.unwrap();
+    h.process(&[create], &[&depositor]).await.unwrap();
+
+    // The new authority only takes over once it accepts.
+    let successor = Keypair::new();
+    assert!(h.process(&[vault_ix::accept_config_authority(&successor.pubkey())], &[&successor]).await.is_err());
+    h.process(&[vault_ix::propose_config_authority(&admin.pubkey(), Some(successor.pubkey()))], &[]).await.unwrap();
+    assert!(h.process(&[vault_ix::accept_config_authority(&outsider.pubkey())], &[&outsider]).await.is_err());
+    assert_eq!(config(&mut h).await.0, admin.pubkey());
+    h.process(&[vault_ix::accept_config_authority(&successor.pubkey())], &[&successor]).await.unwrap();
+    assert_eq!(config(&mut h).await, (successor.pubkey(), false, 6));
+    assert!(h.process(&[vault_ix::set_config_paused(&admin.pubkey(), true)], &[]).await.is_err());
+    h.process(&[vault_ix::set_config_paused(&successor.pubkey(), true)], &[&successor]).await.unwrap();
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+#[derive(Accounts)]
+pub struct AcceptConfigAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"escrow_config"],
+        bump = config.bump,
+        constraint = config.pending_authority == Some(new_authority.key()) @ VaultError::NoPendingAuthority
+    )]
+    pub config: Account<'info, EscrowConfig>,
+    pub new_authority: Signer<'info>,
+}
+pub fn handler(ctx: Context<AcceptConfigAuthority>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let previous_authority = config.authority;
+    config.authority = ctx.accounts.new_authority.key();
+    config.pending_authority = None;
+    let version = config.bump_version()?;
+    emit!(AuthorityTransferred {
+        previous_authority,
+        authority: config.authority,
+        version,
+    });
+    msg!("Escrow config authority transferred from {} to {}", previous_authority, config.authority);
+    Ok(())
+}
\ No newline at end of file
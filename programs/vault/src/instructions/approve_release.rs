@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+#[derive(Accounts)]
+pub struct ApproveRelease<'info> {
+    #[account(
+        seeds = [
+            b"escrow_vault",
+            escrow_vault.owner_program.as_ref(),
+            escrow_vault.vault_id.to_le_bytes().as_ref()
+        ],
+        bump = escrow_vault.bump
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+    #[account(
+        mut,
+        seeds = [
+            b"release_proposal",
+            escrow_vault.key().as_ref(),
+            proposal.proposal_id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump,
+        has_one = escrow_vault
+    )]
+    pub proposal: Account<'info, ReleaseProposal>,
+    pub approver: Signer<'info>,
+}
+pub fn handler(ctx: Context<ApproveRelease>) -> Result<()> {
+    let escrow = &ctx.accounts.escrow_vault;
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = Clock::get()?;
+    let (signers, m) = escrow.release_signers().ok_or(VaultError::InvalidReleaseAuthority)?;
+    require!(
+        signers.contains(&ctx.accounts.approver.key()),
+        VaultError::Unauthorized
+    );
+    require!(
+        !proposal.is_expired(clock.unix_timestamp),
+        VaultError::ProposalExpired
+    );
+    proposal.approve(ctx.accounts.approver.key())?;
+    msg!(
+        "Release proposal {} approved by {} ({}/{})",
+        proposal.proposal_id,
+        ctx.accounts.approver.key(),
+        proposal.approval_count(&signers),
+        m
+    );
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::release_oracle;
+#[derive(Accounts)]
+pub struct CancelEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_vault",
+            escrow_vault.owner_program.as_ref(),
+            escrow_vault.vault_id.to_le_bytes().as_ref()
+        ],
+        bump = escrow_vault.bump
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+    #[account(
+        mut,
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = escrow_vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    #[account(
+        constraint = beneficiary_consent.key() == escrow_vault.beneficiary @ VaultError::Unauthorized
+    )]
+    pub beneficiary_consent: Option<Signer<'info>>,
+    #[account(
+        mut,
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = escrow_vault.beneficiary
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = escrow_vault.depositor
+    )]
+    pub depositor_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelEscrow<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let escrow = &ctx.accounts.escrow_vault;
+    let authority = ctx.accounts.authority.key();
+    let beneficiary_consent = ctx.accounts.beneficiary_consent.is_some() || authority == escrow.beneficiary;
+    require!(
+        escrow.can_cancel(&authority, beneficiary_consent),
+        VaultError::BeneficiaryConsentRequired
+    );
+    let vested = match escrow.status {
+        EscrowStatus::Initialized => 0,
+        EscrowStatus::Active | EscrowStatus::Funded | EscrowStatus::Paused => {
+            release_oracle::available(escrow, ctx.remaining_accounts, clock.unix_timestamp)?
+        }
+        _ => return Err(VaultError::InvalidStatus.into()),
+    };
+    let balance = ctx.accounts.vault_token_account.amount;
+    let (to_beneficiary, to_depositor) = ctx.accounts.escrow_vault.settle(vested, balance)?;
+    let beneficiary_token_account = ctx.accounts.beneficiary_token_account.to_account_info();
+    let depositor_token_account = ctx.accounts.depositor_token_account.to_account_info();
+    transfer_from_vault(&ctx, beneficiary_token_account, to_beneficiary)?;
+    transfer_from_vault(&ctx, depositor_token_account, to_depositor)?;
+    let escrow = &mut ctx.accounts.escrow_vault;
+    escrow.updated_at = clock.unix_timestamp;
+    emit!(EscrowCancelled {
+        escrow_vault: escrow.key(),
+        cancelled_by: authority,
+        to_beneficiary,
+        to_depositor,
+    });
+    msg!(
+        "Escrow vault {} cancelled: {} to beneficiary, {} refunded to depositor",
+        escrow.vault_id,
+        to_beneficiary,
+        to_depositor
+    );
+    Ok(())
+}
+fn transfer_from_vault<'info>(ctx: &Context<CancelEscrow<'info>>, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let escrow = &ctx.accounts.escrow_vault;
+    let owner_program = escrow.owner_program;
+    let vault_id_bytes = escrow.vault_id.to_le_bytes();
+    let seeds = &[
+        b"escrow_vault",
+        owner_program.as_ref(),
+        vault_id_bytes.as_ref(),
+        &[escrow.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault_token_account.to_account_info(),
+        to,
+        authority: escrow.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, amount)
+}
\ No newline at end of file
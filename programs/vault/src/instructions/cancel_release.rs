@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+#[derive(Accounts)]
+pub struct CancelRelease<'info> {
+    #[account(
+        seeds = [
+            b"escrow_vault",
+            escrow_vault.owner_program.as_ref(),
+            escrow_vault.vault_id.to_le_bytes().as_ref()
+        ],
+        bump = escrow_vault.bump
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+    #[account(
+        mut,
+        seeds = [
+            b"release_proposal",
+            escrow_vault.key().as_ref(),
+            proposal.proposal_id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump,
+        has_one = escrow_vault,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, ReleaseProposal>,
+    /// CHECK: Matched against `proposal.proposer`; receives the proposal rent
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+}
+pub fn handler(ctx: Context<CancelRelease>) -> Result<()> {
+    let escrow = &ctx.accounts.escrow_vault;
+    let authority = ctx.accounts.authority.key();
+    let is_signer = escrow
+        .release_signers()
+        .is_some_and(|(signers, _)| signers.contains(&authority));
+    require!(
+        authority == ctx.accounts.proposal.proposer || is_signer,
+        VaultError::Unauthorized
+    );
+    msg!(
+        "Release proposal {} cancelled for vault {}",
+        ctx.accounts.proposal.proposal_id,
+        escrow.vault_id
+    );
+    Ok(())
+}
\ No newline at end of file
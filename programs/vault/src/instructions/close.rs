@@ -20,11 +20,18 @@ pub struct CloseEscrow<'info> {
         associated_token::authority = escrow_vault
     )]
     pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(
+        constraint = authority.key() == escrow_vault.depositor ||
+                    authority.key() == escrow_vault.owner_account
+                    @ VaultError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    /// CHECK: Matched against `escrow_vault.depositor`; receives the refund and rent
     #[account(
         mut,
         constraint = depositor.key() == escrow_vault.depositor @ VaultError::Unauthorized
     )]
-    pub depositor: Signer<'info>,
+    pub depositor: UncheckedAccount<'info>,
     #[account(
         mut,
         associated_token::mint = escrow_vault.token_mint,
@@ -36,12 +43,23 @@ pub struct CloseEscrow<'info> {
 pub fn handler(ctx: Context<CloseEscrow>) -> Result<()> {
     let escrow = &ctx.accounts.escrow_vault;
     require!(
+        escrow.status == EscrowStatus::Initialized ||
         escrow.status == EscrowStatus::Completed || 
         escrow.status == EscrowStatus::Cancelled ||
         escrow.status == EscrowStatus::Active ||
         escrow.status == EscrowStatus::Funded,
         VaultError::InvalidStatus
     );
+    let clock = Clock::get()?;
+    if (escrow.status == EscrowStatus::Active || escrow.status == EscrowStatus::Funded) &&
+        escrow.has_missed_milestones_owed(clock.unix_timestamp)
+    {
+        let as_of = escrow.expires_at.map_or(clock.unix_timestamp, |expires_at| expires_at.min(clock.unix_timestamp));
+        require!(
+            escrow.calculate_available(as_of)? == 0,
+            VaultError::MissedMilestonesUnclaimed
+        );
+    }
     let remaining_balance = ctx.accounts.vault_token_account.amount;
     if remaining_balance > 0 {
         let owner_program = escrow.owner_program;
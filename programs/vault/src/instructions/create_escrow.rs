@@ -65,6 +65,7 @@ pub fn handler(
             VaultError::InvalidTimeRange
         );
     }
+    release_authority.validate()?;
     match &release_schedule {
         ReleaseSchedule::Linear { start, end } => {
             require!(
@@ -77,6 +78,10 @@ pub fn handler(
             );
         },
         ReleaseSchedule::Milestone { conditions } => {
+            require!(
+                conditions.len() <= MAX_MILESTONES,
+                VaultError::InvalidMilestoneConfig
+            );
             let mut total_milestone_amount = 0u64;
             for condition in conditions {
                 total_milestone_amount = total_milestone_amount
@@ -87,6 +92,7 @@ pub fn handler(
                 total_milestone_amount == total_amount,
                 VaultError::InvalidMilestoneConfig
             );
+            ReleaseSchedule::validate_milestone_deadlines(conditions, clock.unix_timestamp)?;
         },
         ReleaseSchedule::Hybrid { 
             linear_portion, 
@@ -102,6 +108,10 @@ pub fn handler(
                 linear_config.end_time - linear_config.start_time <= config.max_escrow_duration,
                 VaultError::InvalidTimeRange
             );
+            require!(
+                milestone_config.len() <= MAX_HYBRID_MILESTONES,
+                VaultError::InvalidMilestoneConfig
+            );
             let mut total_milestone_amount = 0u64;
             for condition in milestone_config {
                 total_milestone_amount = total_milestone_amount
@@ -115,9 +125,20 @@ pub fn handler(
                 combined_total == total_amount && total_milestone_amount == *milestone_portion,
                 VaultError::InvalidMilestoneConfig
             );
+            ReleaseSchedule::validate_milestone_deadlines(milestone_config, clock.unix_timestamp)?;
+        },
+        ReleaseSchedule::Steps { entries } => {
+            ReleaseSchedule::validate_steps(entries, total_amount, config.max_escrow_duration)?;
+        },
+        ReleaseSchedule::Oracle { data, .. } => {
+            require!(
+                data.len() <= MAX_ORACLE_SCHEDULE_DATA,
+                VaultError::UnsupportedSchedule
+            );
         },
         _ => {}
     }
+    escrow.version = ESCROW_VAULT_VERSION;
     escrow.vault_id = vault_id;
     escrow.owner_program = ctx.accounts.owner_program.key();
     escrow.owner_account = ctx.accounts.owner_account.key();
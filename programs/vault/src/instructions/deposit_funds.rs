@@ -36,6 +36,18 @@ pub struct DepositFunds<'info> {
     )]
     pub config: Account<'info, EscrowConfig>,
     pub token_program: Program<'info, Token>,
+    #[account(
+        mut,
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = config.fee_recipient
+    )]
+    pub fee_token_account: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger", escrow_vault.token_mint.as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Option<Account<'info, FeeLedger>>,
 }
 pub fn handler(ctx: Context<DepositFunds>, amount: u64) -> Result<()> {
     let escrow = &mut ctx.accounts.escrow_vault;
@@ -56,18 +68,10 @@ pub fn handler(ctx: Context<DepositFunds>, amount: u64) -> Result<()> {
         expected_total <= escrow.total_amount,
         VaultError::InvalidAmount
     );
-    let fee_amount = if config.fee_basis_points > 0 {
-        (amount as u128)
-            .checked_mul(config.fee_basis_points as u128)
-            .ok_or(VaultError::ArithmeticOverflow)?
-            .checked_div(10000)
-            .ok_or(VaultError::ArithmeticOverflow)? as u64
-    } else {
-        0
-    };
-    let deposit_amount = amount
-        .checked_sub(fee_amount)
-        .ok_or(VaultError::ArithmeticOverflow)?;
+    let (deposit_amount, fee_amount) = config.split_deposit(amount)?;
+    if config.fee_mode == FeeMode::Inclusive && fee_amount > 0 {
+        require!(escrow.supports_inclusive_fee(), VaultError::InclusiveFeeUnsupported);
+    }
     let cpi_accounts = Transfer {
         from: ctx.accounts.depositor_token_account.to_account_info(),
         to: ctx.accounts.vault_token_account.to_account_info(),
@@ -77,15 +81,38 @@ pub fn handler(ctx: Context<DepositFunds>, amount: u64) -> Result<()> {
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     token::transfer(cpi_ctx, deposit_amount)?;
     if fee_amount > 0 {
-        msg!("Platform fee of {} tokens would be collected", fee_amount);
+        let (Some(fee_token_account), Some(fee_ledger)) =
+            (&ctx.accounts.fee_token_account, &mut ctx.accounts.fee_ledger)
+        else {
+            return Err(VaultError::FeeAccountsRequired.into());
+        };
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.depositor_token_account.to_account_info(),
+            to: fee_token_account.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, fee_amount)?;
+        fee_ledger.total_collected = fee_ledger.total_collected
+            .checked_add(fee_amount)
+            .ok_or(VaultError::ArithmeticOverflow)?;
+        if config.fee_mode == FeeMode::Inclusive {
+            escrow.total_amount = escrow.total_amount
+                .checked_sub(fee_amount)
+                .ok_or(VaultError::ArithmeticOverflow)?;
+        }
+        msg!("Platform fee of {} tokens collected ({:?})", fee_amount, config.fee_mode);
     }
-    if current_balance.checked_add(deposit_amount).unwrap() == escrow.total_amount {
+    let funded_amount = current_balance
+        .checked_add(deposit_amount)
+        .ok_or(VaultError::ArithmeticOverflow)?;
+    if funded_amount == escrow.total_amount {
         escrow.status = EscrowStatus::Active;
         msg!("Escrow fully funded and activated");
     } else {
         escrow.status = EscrowStatus::Funded;
         msg!("Partial deposit: {} of {} tokens funded", 
-            current_balance + deposit_amount, 
+            funded_amount, 
             escrow.total_amount
         );
     }
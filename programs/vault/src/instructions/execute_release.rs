@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::release_oracle;
+#[derive(Accounts)]
+pub struct ExecuteRelease<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_vault",
+            escrow_vault.owner_program.as_ref(),
+            escrow_vault.vault_id.to_le_bytes().as_ref()
+        ],
+        bump = escrow_vault.bump
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+    #[account(
+        mut,
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = escrow_vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [
+            b"release_proposal",
+            escrow_vault.key().as_ref(),
+            proposal.proposal_id.to_le_bytes().as_ref()
+        ],
+        bump = proposal.bump,
+        has_one = escrow_vault,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, ReleaseProposal>,
+    /// CHECK: Matched against `proposal.proposer`; receives the proposal rent
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    pub executor: Signer<'info>,
+    #[account(
+        mut,
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = escrow_vault.beneficiary
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteRelease<'info>>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow_vault;
+    let proposal = &ctx.accounts.proposal;
+    let clock = Clock::get()?;
+    require!(
+        escrow.status == EscrowStatus::Active || escrow.status == EscrowStatus::Funded,
+        VaultError::InvalidStatus
+    );
+    let (signers, m) = escrow.release_signers().ok_or(VaultError::InvalidReleaseAuthority)?;
+    require!(
+        signers.contains(&ctx.accounts.executor.key()),
+        VaultError::Unauthorized
+    );
+    require!(
+        !proposal.is_expired(clock.unix_timestamp),
+        VaultError::ProposalExpired
+    );
+    require!(
+        proposal.approval_count(&signers) >= m as usize,
+        VaultError::InsufficientApprovals
+    );
+    let available = release_oracle::available(escrow, ctx.remaining_accounts, clock.unix_timestamp)?;
+    require!(
+        proposal.amount <= available && proposal.amount <= ctx.accounts.vault_token_account.amount,
+        VaultError::InsufficientFunds
+    );
+    let owner_program = escrow.owner_program;
+    let vault_id_bytes = escrow.vault_id.to_le_bytes();
+    let seeds = &[
+        b"escrow_vault",
+        owner_program.as_ref(),
+        vault_id_bytes.as_ref(),
+        &[escrow.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault_token_account.to_account_info(),
+        to: ctx.accounts.beneficiary_token_account.to_account_info(),
+        authority: escrow.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, proposal.amount)?;
+    escrow.released_amount = escrow.released_amount
+        .checked_add(proposal.amount)
+        .ok_or(VaultError::ArithmeticOverflow)?;
+    escrow.updated_at = clock.unix_timestamp;
+    if escrow.is_settled() {
+        escrow.status = EscrowStatus::Completed;
+        msg!("Escrow fully released and completed");
+    }
+    msg!(
+        "Release proposal {} executed: {} tokens from vault {}",
+        proposal.proposal_id,
+        proposal.amount,
+        escrow.vault_id
+    );
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+#[derive(Accounts)]
+pub struct FinalizeMilestone<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_vault",
+            escrow_vault.owner_program.as_ref(),
+            escrow_vault.vault_id.to_le_bytes().as_ref()
+        ],
+        bump = escrow_vault.bump
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+    #[account(
+        mut,
+        seeds = [
+            b"milestone_submission",
+            escrow_vault.key().as_ref(),
+            submission.milestone_id.to_le_bytes().as_ref()
+        ],
+        bump = submission.bump,
+        has_one = escrow_vault,
+        has_one = submitter,
+        close = submitter
+    )]
+    pub submission: Account<'info, MilestoneSubmission>,
+    /// CHECK: Matched against `submission.submitter`; receives the submission rent
+    #[account(mut)]
+    pub submitter: UncheckedAccount<'info>,
+}
+pub fn handler(ctx: Context<FinalizeMilestone>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow_vault;
+    let submission = &ctx.accounts.submission;
+    let clock = Clock::get()?;
+    require!(
+        escrow.status == EscrowStatus::Active || escrow.status == EscrowStatus::Funded,
+        VaultError::InvalidStatus
+    );
+    require!(
+        submission.review_elapsed(escrow.schedule_time(clock.unix_timestamp)),
+        VaultError::ReviewWindowNotElapsed
+    );
+    escrow.release_schedule.complete_milestone(submission.milestone_id, submission.submitted_at)?;
+    escrow.updated_at = clock.unix_timestamp;
+    emit!(MilestoneReviewed {
+        escrow_vault: escrow.key(),
+        milestone_id: submission.milestone_id,
+        deliverable_hash: submission.deliverable_hash,
+        review: MilestoneReview::Approve,
+        finalized_by_timeout: true,
+    });
+    msg!(
+        "Milestone {} of vault {} approved after its review window",
+        submission.milestone_id,
+        escrow.vault_id
+    );
+    Ok(())
+}
\ No newline at end of file
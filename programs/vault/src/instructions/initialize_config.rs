@@ -16,29 +16,17 @@ pub struct InitializeConfig<'info> {
     pub fee_recipient: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
-pub fn handler(
-    ctx: Context<InitializeConfig>,
-    fee_basis_points: u16,
-    min_escrow_amount: u64,
-    max_escrow_duration: i64,
-) -> Result<()> {
+pub fn handler(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+    params.validate()?;
     let config = &mut ctx.accounts.config;
-    require!(
-        fee_basis_points <= 10000, 
-        VaultError::InvalidAmount
-    );
-    require!(
-        max_escrow_duration > 0,
-        VaultError::InvalidTimeRange
-    );
+    config.layout_version = ESCROW_CONFIG_VERSION;
     config.authority = ctx.accounts.authority.key();
-    config.fee_basis_points = fee_basis_points;
+    config.pending_authority = None;
     config.fee_recipient = ctx.accounts.fee_recipient.key();
-    config.min_escrow_amount = min_escrow_amount;
-    config.max_escrow_duration = max_escrow_duration;
+    config.apply(&params);
     config.paused = false;
     config.version = 1;
     config.bump = ctx.bumps.config;
-    msg!("Escrow config initialized with {} basis points fee", fee_basis_points);
+    msg!("Escrow config initialized with {} basis points fee ({:?})", params.fee_basis_points, params.fee_mode);
     Ok(())
 }
\ No newline at end of file
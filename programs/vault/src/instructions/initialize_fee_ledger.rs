@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::state::*;
+#[derive(Accounts)]
+pub struct InitializeFeeLedger<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = FeeLedger::LEN,
+        seeds = [b"fee_ledger", mint.key().as_ref()],
+        bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    pub mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+pub fn handler(ctx: Context<InitializeFeeLedger>) -> Result<()> {
+    let fee_ledger = &mut ctx.accounts.fee_ledger;
+    fee_ledger.mint = ctx.accounts.mint.key();
+    fee_ledger.total_collected = 0;
+    fee_ledger.bump = ctx.bumps.fee_ledger;
+    msg!("Fee ledger initialized for mint {}", fee_ledger.mint);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::state::*;
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: Decoded as the version 1 layout in the handler; must be owned by this program
+    #[account(
+        mut,
+        seeds = [b"escrow_config"],
+        bump,
+        owner = crate::ID
+    )]
+    pub config: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+pub fn handler(ctx: Context<MigrateConfig>, params: ConfigParams) -> Result<()> {
+    params.validate()?;
+    let config_info = ctx.accounts.config.to_account_info();
+    let legacy = EscrowConfigV1::from_bytes(&config_info.try_borrow_data()?)?;
+    require_keys_eq!(
+        legacy.authority,
+        ctx.accounts.authority.key(),
+        VaultError::Unauthorized
+    );
+    let mut config = legacy.upgrade(&params);
+    let version = config.bump_version()?;
+    let required_lamports = Rent::get()?.minimum_balance(EscrowConfig::LEN);
+    let shortfall = required_lamports.saturating_sub(config_info.lamports());
+    if shortfall > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.authority.to_account_info(),
+            to: config_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, shortfall)?;
+    }
+    config_info.resize(EscrowConfig::LEN)?;
+    let mut data = config_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    config.try_serialize(&mut writer)?;
+    emit!(ConfigUpdated {
+        fee_basis_points: params.fee_basis_points,
+        fee_mode: params.fee_mode,
+        fee_recipient: config.fee_recipient,
+        min_escrow_amount: params.min_escrow_amount,
+        max_escrow_duration: params.max_escrow_duration,
+        expiry_policy: params.expiry_policy,
+        unfunded_grace_period: params.unfunded_grace_period,
+        milestone_review_window: params.milestone_review_window,
+        version,
+    });
+    msg!("Escrow config migrated to layout v{}", ESCROW_CONFIG_VERSION);
+    Ok(())
+}
\ No newline at end of file
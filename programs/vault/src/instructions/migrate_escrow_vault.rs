@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::state::*;
+#[derive(Accounts)]
+pub struct MigrateEscrowVault<'info> {
+    /// CHECK: Decoded by layout version in the handler; must be owned by this program
+    #[account(mut, owner = crate::ID)]
+    pub escrow_vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+pub fn handler(ctx: Context<MigrateEscrowVault>) -> Result<()> {
+    let escrow_info = ctx.accounts.escrow_vault.to_account_info();
+    let (escrow, from_version) = EscrowVault::from_versioned_bytes(&escrow_info.try_borrow_data()?)?;
+    require!(
+        from_version < ESCROW_VAULT_VERSION,
+        VaultError::AlreadyMigrated
+    );
+    if escrow_info.data_len() < EscrowVault::LEN {
+        let required_lamports = Rent::get()?.minimum_balance(EscrowVault::LEN);
+        let shortfall = required_lamports.saturating_sub(escrow_info.lamports());
+        if shortfall > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: escrow_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_ctx, shortfall)?;
+        }
+        escrow_info.resize(EscrowVault::LEN)?;
+    }
+    let mut data = escrow_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    escrow.try_serialize(&mut writer)?;
+    msg!("Escrow vault {} migrated from v{} to v{}", 
+        escrow.vault_id, 
+        from_version, 
+        ESCROW_VAULT_VERSION
+    );
+    Ok(())
+}
@@ -5,10 +5,54 @@ pub mod withdraw_available;
 pub mod update_release_schedule;
 pub mod release_milestone;
 pub mod close;
+pub mod migrate_escrow_vault;
+pub mod propose_release;
+pub mod approve_release;
+pub mod execute_release;
+pub mod cancel_release;
+pub mod initialize_fee_ledger;
+pub mod update_config;
+pub mod set_config_paused;
+pub mod propose_config_authority;
+pub mod accept_config_authority;
+pub mod raise_dispute;
+pub mod resolve_dispute;
+pub mod cancel_escrow;
+pub mod reclaim_expired;
+pub mod close_stale_escrow;
+pub mod pause_escrow;
+pub mod resume_escrow;
+pub mod refund_missed_milestones;
+pub mod submit_milestone;
+pub mod review_milestone;
+pub mod finalize_milestone;
+pub mod migrate_config;
 pub use initialize_config::*;
 pub use create_escrow::*;
 pub use deposit_funds::*;
 pub use withdraw_available::*;
 pub use update_release_schedule::*;
 pub use release_milestone::*;
-pub use close::*;
\ No newline at end of file
+pub use close::*;
+pub use migrate_escrow_vault::*;
+pub use propose_release::*;
+pub use approve_release::*;
+pub use execute_release::*;
+pub use cancel_release::*;
+pub use initialize_fee_ledger::*;
+pub use update_config::*;
+pub use set_config_paused::*;
+pub use propose_config_authority::*;
+pub use accept_config_authority::*;
+pub use raise_dispute::*;
+pub use resolve_dispute::*;
+pub use cancel_escrow::*;
+pub use reclaim_expired::*;
+pub use close_stale_escrow::*;
+pub use pause_escrow::*;
+pub use resume_escrow::*;
+pub use refund_missed_milestones::*;
+pub use submit_milestone::*;
+pub use review_milestone::*;
+pub use finalize_milestone::*;
+pub use migrate_config::*;
\ No newline at end of file
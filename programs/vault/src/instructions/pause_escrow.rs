@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+#[derive(Accounts)]
+pub struct PauseEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_vault",
+            escrow_vault.owner_program.as_ref(),
+            escrow_vault.vault_id.to_le_bytes().as_ref()
+        ],
+        bump = escrow_vault.bump,
+        constraint = escrow_vault.can_pause(&authority.key()) @ VaultError::Unauthorized
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+    pub authority: Signer<'info>,
+}
+pub fn handler(ctx: Context<PauseEscrow>) -> Result<()> {
+    let clock = Clock::get()?;
+    let escrow = &mut ctx.accounts.escrow_vault;
+    escrow.pause(clock.unix_timestamp)?;
+    escrow.updated_at = clock.unix_timestamp;
+    emit!(EscrowPaused {
+        escrow_vault: escrow.key(),
+        paused_by: ctx.accounts.authority.key(),
+        paused_at: clock.unix_timestamp,
+    });
+    msg!("Escrow vault {} paused at {}", escrow.vault_id, clock.unix_timestamp);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+#[derive(Accounts)]
+pub struct ProposeConfigAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"escrow_config"],
+        bump = config.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub config: Account<'info, EscrowConfig>,
+    pub authority: Signer<'info>,
+}
+pub fn handler(ctx: Context<ProposeConfigAuthority>, new_authority: Option<Pubkey>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.pending_authority = new_authority;
+    let version = config.bump_version()?;
+    emit!(AuthorityTransferProposed {
+        authority: config.authority,
+        pending_authority: new_authority,
+        version,
+    });
+    msg!("Escrow config authority transfer proposed to {:?}", new_authority);
+    Ok(())
+}
\ No newline at end of file
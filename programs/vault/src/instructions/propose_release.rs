@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ProposeRelease<'info> {
+    #[account(
+        seeds = [
+            b"escrow_vault",
+            escrow_vault.owner_program.as_ref(),
+            escrow_vault.vault_id.to_le_bytes().as_ref()
+        ],
+        bump = escrow_vault.bump
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+    #[account(
+        init,
+        payer = proposer,
+        space = ReleaseProposal::LEN,
+        seeds = [
+            b"release_proposal",
+            escrow_vault.key().as_ref(),
+            proposal_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, ReleaseProposal>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+pub fn handler(
+    ctx: Context<ProposeRelease>,
+    proposal_id: u64,
+    amount: u64,
+    lifetime: i64,
+) -> Result<()> {
+    let escrow = &ctx.accounts.escrow_vault;
+    let clock = Clock::get()?;
+    require!(
+        escrow.status == EscrowStatus::Active || escrow.status == EscrowStatus::Funded,
+        VaultError::InvalidStatus
+    );
+    let (signers, _) = escrow.release_signers().ok_or(VaultError::InvalidReleaseAuthority)?;
+    require!(
+        signers.contains(&ctx.accounts.proposer.key()),
+        VaultError::Unauthorized
+    );
+    require!(amount > 0, VaultError::InvalidAmount);
+    require!(
+        lifetime > 0 && lifetime <= MAX_RELEASE_PROPOSAL_LIFETIME,
+        VaultError::InvalidTimeRange
+    );
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.escrow_vault = escrow.key();
+    proposal.proposal_id = proposal_id;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.amount = amount;
+    proposal.approvals = vec![ctx.accounts.proposer.key()];
+    proposal.created_at = clock.unix_timestamp;
+    proposal.expires_at = clock.unix_timestamp
+        .checked_add(lifetime)
+        .ok_or(VaultError::ArithmeticOverflow)?;
+    proposal.bump = ctx.bumps.proposal;
+    msg!(
+        "Release proposal {} for {} tokens from vault {}, expires at {}",
+        proposal_id,
+        amount,
+        escrow.vault_id,
+        proposal.expires_at
+    );
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::*;
+#[derive(Accounts)]
+pub struct RaiseDispute<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_vault",
+            escrow_vault.owner_program.as_ref(),
+            escrow_vault.vault_id.to_le_bytes().as_ref()
+        ],
+        bump = escrow_vault.bump
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+    #[account(
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = escrow_vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = raised_by,
+        space = Dispute::LEN,
+        seeds = [b"dispute", escrow_vault.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        mut,
+        constraint = raised_by.key() == escrow_vault.depositor ||
+                    raised_by.key() == escrow_vault.beneficiary
+                    @ VaultError::Unauthorized
+    )]
+    pub raised_by: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+pub fn handler(ctx: Context<RaiseDispute>, amount: u64, evidence_hash: [u8; 32]) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow_vault;
+    let clock = Clock::get()?;
+    require!(
+        escrow.status == EscrowStatus::Active || escrow.status == EscrowStatus::Funded,
+        VaultError::InvalidStatus
+    );
+    require!(escrow.arbiter.is_some(), VaultError::NoArbiter);
+    require!(amount > 0, VaultError::InvalidAmount);
+    require!(
+        amount <= ctx.accounts.vault_token_account.amount,
+        VaultError::InsufficientFunds
+    );
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.escrow_vault = escrow.key();
+    dispute.raised_by = ctx.accounts.raised_by.key();
+    dispute.amount = amount;
+    dispute.evidence_hash = evidence_hash;
+    dispute.previous_status = escrow.status.clone();
+    dispute.raised_at = clock.unix_timestamp;
+    dispute.bump = ctx.bumps.dispute;
+    escrow.locked_amount = amount;
+    escrow.status = EscrowStatus::Disputed;
+    escrow.updated_at = clock.unix_timestamp;
+    emit!(DisputeRaised {
+        escrow_vault: escrow.key(),
+        raised_by: dispute.raised_by,
+        amount,
+        evidence_hash,
+    });
+    msg!("Dispute raised on vault {} locking {} tokens", escrow.vault_id, amount);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::release_oracle;
+#[derive(Accounts)]
+pub struct ReclaimExpired<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_vault",
+            escrow_vault.owner_program.as_ref(),
+            escrow_vault.vault_id.to_le_bytes().as_ref()
+        ],
+        bump = escrow_vault.bump
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+    #[account(
+        mut,
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = escrow_vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"escrow_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, EscrowConfig>,
+    #[account(
+        mut,
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = escrow_vault.beneficiary
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = escrow_vault.depositor
+    )]
+    pub depositor_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ReclaimExpired<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let escrow = &ctx.accounts.escrow_vault;
+    let expires_at = escrow.expires_at.ok_or(VaultError::NotExpired)?;
+    require!(clock.unix_timestamp > expires_at, VaultError::NotExpired);
+    require!(
+        matches!(
+            escrow.status,
+            EscrowStatus::Initialized | EscrowStatus::Active | EscrowStatus::Funded | EscrowStatus::Paused
+        ),
+        VaultError::InvalidStatus
+    );
+    let expiry_policy = ctx.accounts.config.expiry_policy;
+    let vested = if expiry_policy == ExpiryPolicy::PayVested && escrow.status != EscrowStatus::Initialized {
+        release_oracle::available(escrow, ctx.remaining_accounts, expires_at)?
+    } else {
+        0
+    };
+    let balance = ctx.accounts.vault_token_account.amount;
+    let (to_beneficiary, to_depositor) = ctx.accounts.escrow_vault.settle(vested, balance)?;
+    let beneficiary_token_account = ctx.accounts.beneficiary_token_account.to_account_info();
+    let depositor_token_account = ctx.accounts.depositor_token_account.to_account_info();
+    transfer_from_vault(&ctx, beneficiary_token_account, to_beneficiary)?;
+    transfer_from_vault(&ctx, depositor_token_account, to_depositor)?;
+    let escrow = &mut ctx.accounts.escrow_vault;
+    escrow.updated_at = clock.unix_timestamp;
+    emit!(EscrowReclaimed {
+        escrow_vault: escrow.key(),
+        expiry_policy,
+        to_beneficiary,
+        to_depositor,
+    });
+    msg!(
+        "Expired escrow vault {} reclaimed ({:?}): {} to beneficiary, {} refunded to depositor",
+        escrow.vault_id,
+        expiry_policy,
+        to_beneficiary,
+        to_depositor
+    );
+    Ok(())
+}
+fn transfer_from_vault<'info>(ctx: &Context<ReclaimExpired<'info>>, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let escrow = &ctx.accounts.escrow_vault;
+    let owner_program = escrow.owner_program;
+    let vault_id_bytes = escrow.vault_id.to_le_bytes();
+    let seeds = &[
+        b"escrow_vault",
+        owner_program.as_ref(),
+        vault_id_bytes.as_ref(),
+        &[escrow.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault_token_account.to_account_info(),
+        to,
+        authority: escrow.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, amount)
+}
\ No newline at end of file
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+#[derive(Accounts)]
+pub struct RefundMissedMilestones<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_vault",
+            escrow_vault.owner_program.as_ref(),
+            escrow_vault.vault_id.to_le_bytes().as_ref()
+        ],
+        bump = escrow_vault.bump
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+    #[account(
+        mut,
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = escrow_vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = escrow_vault.depositor
+    )]
+    pub depositor_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+pub fn handler(ctx: Context<RefundMissedMilestones>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow_vault;
+    let clock = Clock::get()?;
+    require!(
+        escrow.status == EscrowStatus::Active ||
+        escrow.status == EscrowStatus::Funded ||
+        escrow.status == EscrowStatus::Paused,
+        VaultError::InvalidStatus
+    );
+    let (milestone_ids, amount) = escrow.refund_missed_milestones(clock.unix_timestamp)?;
+    require!(
+        amount <= ctx.accounts.vault_token_account.amount,
+        VaultError::InsufficientFunds
+    );
+    let owner_program = escrow.owner_program;
+    let vault_id_bytes = escrow.vault_id.to_le_bytes();
+    let seeds = &[
+        b"escrow_vault",
+        owner_program.as_ref(),
+        vault_id_bytes.as_ref(),
+        &[escrow.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault_token_account.to_account_info(),
+        to: ctx.accounts.depositor_token_account.to_account_info(),
+        authority: escrow.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, amount)?;
+    escrow.refunded_amount = escrow.refunded_amount
+        .checked_add(amount)
+        .ok_or(VaultError::ArithmeticOverflow)?;
+    escrow.updated_at = clock.unix_timestamp;
+    if escrow.is_settled() {
+        escrow.status = EscrowStatus::Completed;
+        msg!("Escrow fully settled and completed");
+    }
+    msg!(
+        "Refunded {} tokens for {} missed milestones of vault {}",
+        amount,
+        milestone_ids.len(),
+        escrow.vault_id
+    );
+    emit!(MissedMilestonesRefunded {
+        escrow_vault: escrow.key(),
+        milestone_ids,
+        amount,
+    });
+    Ok(())
+}
\ No newline at end of file
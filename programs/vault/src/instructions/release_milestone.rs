@@ -21,6 +21,7 @@ pub fn handler(ctx: Context<ReleaseMilestone>, milestone_id: u32) -> Result<()>
         escrow.status == EscrowStatus::Active || escrow.status == EscrowStatus::Funded,
         VaultError::InvalidStatus
     );
+    let schedule_time = escrow.schedule_time(clock.unix_timestamp);
     let mut found = false;
     let mut already_completed = false;
     match &mut escrow.release_schedule {
@@ -36,6 +37,14 @@ pub fn handler(ctx: Context<ReleaseMilestone>, milestone_id: u32) -> Result<()>
                         ctx.accounts.authority.key() == condition.required_approval,
                         VaultError::Unauthorized
                     );
+                    require!(
+                        !condition.is_missed(schedule_time),
+                        VaultError::MilestoneDeadlinePassed
+                    );
+                    require!(
+                        !condition.is_submitted,
+                        VaultError::MilestoneSubmissionPending
+                    );
                     condition.is_completed = true;
                     break;
                 }
@@ -53,6 +62,14 @@ pub fn handler(ctx: Context<ReleaseMilestone>, milestone_id: u32) -> Result<()>
                         ctx.accounts.authority.key() == condition.required_approval,
                         VaultError::Unauthorized
                     );
+                    require!(
+                        !condition.is_missed(schedule_time),
+                        VaultError::MilestoneDeadlinePassed
+                    );
+                    require!(
+                        !condition.is_submitted,
+                        VaultError::MilestoneSubmissionPending
+                    );
                     condition.is_completed = true;
                     break;
                 }
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_vault",
+            escrow_vault.owner_program.as_ref(),
+            escrow_vault.vault_id.to_le_bytes().as_ref()
+        ],
+        bump = escrow_vault.bump
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+    #[account(
+        mut,
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = escrow_vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"dispute", escrow_vault.key().as_ref()],
+        bump = dispute.bump,
+        has_one = escrow_vault,
+        has_one = raised_by,
+        close = raised_by
+    )]
+    pub dispute: Account<'info, Dispute>,
+    /// CHECK: Matched against `dispute.raised_by`; receives the dispute rent
+    #[account(mut)]
+    pub raised_by: UncheckedAccount<'info>,
+    #[account(
+        constraint = escrow_vault.arbiter == Some(arbiter.key()) @ VaultError::Unauthorized
+    )]
+    pub arbiter: Signer<'info>,
+    #[account(
+        mut,
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = escrow_vault.beneficiary
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = escrow_vault.depositor
+    )]
+    pub depositor_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+pub fn handler(ctx: Context<ResolveDispute>, resolution: DisputeResolution) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.escrow_vault.status == EscrowStatus::Disputed,
+        VaultError::InvalidStatus
+    );
+    let locked = ctx.accounts.escrow_vault.locked_amount;
+    let (to_beneficiary, to_depositor) = resolution.split(locked)?;
+    let beneficiary_token_account = ctx.accounts.beneficiary_token_account.to_account_info();
+    let depositor_token_account = ctx.accounts.depositor_token_account.to_account_info();
+    transfer_locked(&ctx, beneficiary_token_account, to_beneficiary)?;
+    transfer_locked(&ctx, depositor_token_account, to_depositor)?;
+    let escrow = &mut ctx.accounts.escrow_vault;
+    escrow.released_amount = escrow.released_amount
+        .checked_add(to_beneficiary)
+        .ok_or(VaultError::ArithmeticOverflow)?;
+    escrow.refunded_amount = escrow.refunded_amount
+        .checked_add(to_depositor)
+        .ok_or(VaultError::ArithmeticOverflow)?;
+    escrow.locked_amount = 0;
+    escrow.status = if escrow.is_settled() {
+        EscrowStatus::Completed
+    } else {
+        ctx.accounts.dispute.previous_status.clone()
+    };
+    escrow.updated_at = clock.unix_timestamp;
+    emit!(DisputeResolved {
+        escrow_vault: escrow.key(),
+        arbiter: ctx.accounts.arbiter.key(),
+        to_beneficiary,
+        to_depositor,
+        status: escrow.status.clone(),
+    });
+    msg!(
+        "Dispute on vault {} resolved: {} to beneficiary, {} to depositor",
+        escrow.vault_id,
+        to_beneficiary,
+        to_depositor
+    );
+    Ok(())
+}
+fn transfer_locked<'info>(ctx: &Context<ResolveDispute<'info>>, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let escrow = &ctx.accounts.escrow_vault;
+    let owner_program = escrow.owner_program;
+    let vault_id_bytes = escrow.vault_id.to_le_bytes();
+    let seeds = &[
+        b"escrow_vault",
+        owner_program.as_ref(),
+        vault_id_bytes.as_ref(),
+        &[escrow.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault_token_account.to_account_info(),
+        to,
+        authority: escrow.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, amount)
+}
\ No newline at end of file
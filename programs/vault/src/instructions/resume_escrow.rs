@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::*;
+#[derive(Accounts)]
+pub struct ResumeEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_vault",
+            escrow_vault.owner_program.as_ref(),
+            escrow_vault.vault_id.to_le_bytes().as_ref()
+        ],
+        bump = escrow_vault.bump,
+        constraint = escrow_vault.can_pause(&authority.key()) @ VaultError::Unauthorized
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+    #[account(
+        associated_token::mint = escrow_vault.token_mint,
+        associated_token::authority = escrow_vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+}
+pub fn handler(ctx: Context<ResumeEscrow>) -> Result<()> {
+    let clock = Clock::get()?;
+    let escrow = &mut ctx.accounts.escrow_vault;
+    let funded = ctx.accounts.vault_token_account.amount
+        .checked_add(escrow.released_amount)
+        .and_then(|amount| amount.checked_add(escrow.refunded_amount))
+        .ok_or(VaultError::ArithmeticOverflow)?;
+    let fully_funded = funded >= escrow.total_amount;
+    escrow.resume(clock.unix_timestamp, fully_funded)?;
+    escrow.updated_at = clock.unix_timestamp;
+    emit!(EscrowResumed {
+        escrow_vault: escrow.key(),
+        resumed_by: ctx.accounts.authority.key(),
+        total_paused_duration: escrow.total_paused_duration,
+    });
+    msg!(
+        "Escrow vault {} resumed, paused for {} seconds in total",
+        escrow.vault_id,
+        escrow.total_paused_duration
+    );
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+#[derive(Accounts)]
+pub struct ReviewMilestone<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_vault",
+            escrow_vault.owner_program.as_ref(),
+            escrow_vault.vault_id.to_le_bytes().as_ref()
+        ],
+        bump = escrow_vault.bump
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+    #[account(
+        mut,
+        seeds = [
+            b"milestone_submission",
+            escrow_vault.key().as_ref(),
+            submission.milestone_id.to_le_bytes().as_ref()
+        ],
+        bump = submission.bump,
+        has_one = escrow_vault,
+        has_one = submitter,
+        close = submitter
+    )]
+    pub submission: Account<'info, MilestoneSubmission>,
+    /// CHECK: Matched against `submission.submitter`; receives the submission rent
+    #[account(mut)]
+    pub submitter: UncheckedAccount<'info>,
+    pub approver: Signer<'info>,
+}
+pub fn handler(ctx: Context<ReviewMilestone>, review: MilestoneReview) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow_vault;
+    let submission = &ctx.accounts.submission;
+    let clock = Clock::get()?;
+    require!(
+        escrow.status == EscrowStatus::Active || escrow.status == EscrowStatus::Funded,
+        VaultError::InvalidStatus
+    );
+    let condition = escrow.release_schedule.open_milestone(submission.milestone_id, submission.submitted_at)?;
+    require!(
+        ctx.accounts.approver.key() == condition.required_approval,
+        VaultError::Unauthorized
+    );
+    if review == MilestoneReview::Approve {
+        escrow.release_schedule.complete_milestone(submission.milestone_id, submission.submitted_at)?;
+    } else {
+        escrow.release_schedule.milestone_mut(submission.milestone_id)?.is_submitted = false;
+    }
+    escrow.updated_at = clock.unix_timestamp;
+    emit!(MilestoneReviewed {
+        escrow_vault: escrow.key(),
+        milestone_id: submission.milestone_id,
+        deliverable_hash: submission.deliverable_hash,
+        review,
+        finalized_by_timeout: false,
+    });
+    msg!(
+        "Milestone {} of vault {} reviewed: {:?}",
+        submission.milestone_id,
+        escrow.vault_id,
+        review
+    );
+    Ok(())
+}
\ No newline at end of file
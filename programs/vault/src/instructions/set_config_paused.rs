@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+#[derive(Accounts)]
+pub struct SetConfigPaused<'info> {
+    #[account(
+        mut,
+        seeds = [b"escrow_config"],
+        bump = config.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub config: Account<'info, EscrowConfig>,
+    pub authority: Signer<'info>,
+}
+pub fn handler(ctx: Context<SetConfigPaused>, paused: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.paused = paused;
+    let version = config.bump_version()?;
+    emit!(ConfigPausedChanged { paused, version });
+    msg!("Escrow config {}", if paused { "paused" } else { "unpaused" });
+    Ok(())
+}
\ No newline at end of file
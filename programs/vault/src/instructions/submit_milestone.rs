@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+#[derive(Accounts)]
+#[instruction(milestone_id: u32)]
+pub struct SubmitMilestone<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_vault",
+            escrow_vault.owner_program.as_ref(),
+            escrow_vault.vault_id.to_le_bytes().as_ref()
+        ],
+        bump = escrow_vault.bump
+    )]
+    pub escrow_vault: Account<'info, EscrowVault>,
+    #[account(
+        init,
+        payer = beneficiary,
+        space = MilestoneSubmission::LEN,
+        seeds = [
+            b"milestone_submission",
+            escrow_vault.key().as_ref(),
+            milestone_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub submission: Account<'info, MilestoneSubmission>,
+    #[account(
+        mut,
+        constraint = beneficiary.key() == escrow_vault.beneficiary @ VaultError::Unauthorized
+    )]
+    pub beneficiary: Signer<'info>,
+    #[account(
+        seeds = [b"escrow_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, EscrowConfig>,
+    pub system_program: Program<'info, System>,
+}
+pub fn handler(ctx: Context<SubmitMilestone>, milestone_id: u32, deliverable_hash: [u8; 32]) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow_vault;
+    let clock = Clock::get()?;
+    require!(
+        escrow.status == EscrowStatus::Active || escrow.status == EscrowStatus::Funded,
+        VaultError::InvalidStatus
+    );
+    let (submitted_at, review_deadline) = escrow.submit_milestone(
+        milestone_id,
+        clock.unix_timestamp,
+        ctx.accounts.config.milestone_review_window,
+    )?;
+    escrow.updated_at = clock.unix_timestamp;
+    let submission = &mut ctx.accounts.submission;
+    submission.escrow_vault = escrow.key();
+    submission.milestone_id = milestone_id;
+    submission.submitter = ctx.accounts.beneficiary.key();
+    submission.deliverable_hash = deliverable_hash;
+    submission.submitted_at = submitted_at;
+    submission.review_deadline = review_deadline;
+    submission.bump = ctx.bumps.submission;
+    emit!(MilestoneSubmitted {
+        escrow_vault: escrow.key(),
+        milestone_id,
+        deliverable_hash,
+        review_deadline: submission.review_deadline,
+    });
+    msg!(
+        "Milestone {} submitted for vault {}, review due by {}",
+        milestone_id,
+        escrow.vault_id,
+        submission.review_deadline
+    );
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"escrow_config"],
+        bump = config.bump,
+        has_one = authority @ VaultError::Unauthorized
+    )]
+    pub config: Account<'info, EscrowConfig>,
+    pub authority: Signer<'info>,
+    /// CHECK: The fee recipient only owns the token accounts that receive platform fees
+    pub fee_recipient: UncheckedAccount<'info>,
+}
+pub fn handler(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+    params.validate()?;
+    let config = &mut ctx.accounts.config;
+    config.fee_recipient = ctx.accounts.fee_recipient.key();
+    config.apply(&params);
+    let version = config.bump_version()?;
+    emit!(ConfigUpdated {
+        fee_basis_points: params.fee_basis_points,
+        fee_mode: params.fee_mode,
+        fee_recipient: config.fee_recipient,
+        min_escrow_amount: params.min_escrow_amount,
+        max_escrow_duration: params.max_escrow_duration,
+        expiry_policy: params.expiry_policy,
+        unfunded_grace_period: params.unfunded_grace_period,
+        milestone_review_window: params.milestone_review_window,
+        version,
+    });
+    msg!("Escrow config updated to version {}", version);
+    Ok(())
+}
\ No newline at end of file
@@ -51,6 +51,10 @@ pub fn handler(
             );
         },
         ReleaseSchedule::Milestone { conditions } => {
+            require!(
+                conditions.len() <= MAX_MILESTONES,
+                VaultError::InvalidMilestoneConfig
+            );
             let mut total_milestone_amount = 0u64;
             for condition in conditions {
                 total_milestone_amount = total_milestone_amount
@@ -61,6 +65,7 @@ pub fn handler(
                 total_milestone_amount == escrow.total_amount,
                 VaultError::InvalidMilestoneConfig
             );
+            ReleaseSchedule::validate_milestone_deadlines(conditions, escrow.schedule_time(clock.unix_timestamp))?;
         },
         ReleaseSchedule::Hybrid { 
             linear_portion, 
@@ -72,6 +77,10 @@ pub fn handler(
                 linear_config.start_time < linear_config.end_time,
                 VaultError::InvalidTimeRange
             );
+            require!(
+                milestone_config.len() <= MAX_HYBRID_MILESTONES,
+                VaultError::InvalidMilestoneConfig
+            );
             let mut total_milestone_amount = 0u64;
             for condition in milestone_config {
                 total_milestone_amount = total_milestone_amount
@@ -86,6 +95,16 @@ pub fn handler(
                 total_milestone_amount == *milestone_portion,
                 VaultError::InvalidMilestoneConfig
             );
+            ReleaseSchedule::validate_milestone_deadlines(milestone_config, escrow.schedule_time(clock.unix_timestamp))?;
+        },
+        ReleaseSchedule::Steps { entries } => {
+            ReleaseSchedule::validate_steps(entries, escrow.total_amount, config.max_escrow_duration)?;
+        },
+        ReleaseSchedule::Oracle { data, .. } => {
+            require!(
+                data.len() <= MAX_ORACLE_SCHEDULE_DATA,
+                VaultError::UnsupportedSchedule
+            );
         },
         _ => {}
     }
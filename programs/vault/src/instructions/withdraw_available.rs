@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
+use crate::release_oracle;
 #[derive(Accounts)]
 pub struct WithdrawAvailable<'info> {
     #[account(
@@ -29,7 +30,7 @@ pub struct WithdrawAvailable<'info> {
     pub withdrawer_token_account: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
 }
-pub fn handler(ctx: Context<WithdrawAvailable>, max_amount: Option<u64>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawAvailable<'info>>, max_amount: Option<u64>) -> Result<()> {
     let escrow = &mut ctx.accounts.escrow_vault;
     let clock = Clock::get()?;
     require!(
@@ -38,7 +39,7 @@ pub fn handler(ctx: Context<WithdrawAvailable>, max_amount: Option<u64>) -> Resu
     );
     let can_withdraw = escrow.can_withdraw(&ctx.accounts.withdrawer.key())?;
     require!(can_withdraw, VaultError::Unauthorized);
-    let available = escrow.calculate_available(clock.unix_timestamp)?;
+    let available = release_oracle::available(escrow, ctx.remaining_accounts, clock.unix_timestamp)?;
     let withdraw_amount = if let Some(max) = max_amount {
         available.min(max)
     } else {
@@ -71,7 +72,7 @@ pub fn handler(ctx: Context<WithdrawAvailable>, max_amount: Option<u64>) -> Resu
         .checked_add(withdraw_amount)
         .ok_or(VaultError::ArithmeticOverflow)?;
     escrow.updated_at = clock.unix_timestamp;
-    if escrow.released_amount == escrow.total_amount {
+    if escrow.is_settled() {
         escrow.status = EscrowStatus::Completed;
         msg!("Escrow fully withdrawn and completed");
     }
@@ -1,24 +1,15 @@
 use anchor_lang::prelude::*;
 pub mod state;
 pub mod instructions;
+pub mod release_oracle;
 use instructions::*;
 use state::*;
 declare_id!("3X4cA5KNBo6yYw2aLwRgVPaX3RshUR62Y2Bo8GyXzYWg");
 #[program]
 pub mod vault {
     use super::*;
-    pub fn initialize_config(
-        ctx: Context<InitializeConfig>,
-        fee_basis_points: u16,
-        min_escrow_amount: u64,
-        max_escrow_duration: i64,
-    ) -> Result<()> {
-        instructions::initialize_config::handler(
-            ctx,
-            fee_basis_points,
-            min_escrow_amount,
-            max_escrow_duration,
-        )
+    pub fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+        instructions::initialize_config::handler(ctx, params)
     }
     pub fn create_escrow(
         ctx: Context<CreateEscrow>,
@@ -45,8 +36,8 @@ pub mod vault {
     ) -> Result<()> {
         instructions::deposit_funds::handler(ctx, amount)
     }
-    pub fn withdraw_available(
-        ctx: Context<WithdrawAvailable>,
+    pub fn withdraw_available<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawAvailable<'info>>,
         max_amount: Option<u64>,
     ) -> Result<()> {
         instructions::withdraw_available::handler(ctx, max_amount)
@@ -66,4 +57,89 @@ pub mod vault {
     ) -> Result<()> {
         instructions::release_milestone::handler(ctx, milestone_id)
     }
+    pub fn migrate_escrow_vault(ctx: Context<MigrateEscrowVault>) -> Result<()> {
+        instructions::migrate_escrow_vault::handler(ctx)
+    }
+    pub fn propose_release(
+        ctx: Context<ProposeRelease>,
+        proposal_id: u64,
+        amount: u64,
+        lifetime: i64,
+    ) -> Result<()> {
+        instructions::propose_release::handler(ctx, proposal_id, amount, lifetime)
+    }
+    pub fn approve_release(ctx: Context<ApproveRelease>) -> Result<()> {
+        instructions::approve_release::handler(ctx)
+    }
+    pub fn execute_release<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteRelease<'info>>) -> Result<()> {
+        instructions::execute_release::handler(ctx)
+    }
+    pub fn cancel_release(ctx: Context<CancelRelease>) -> Result<()> {
+        instructions::cancel_release::handler(ctx)
+    }
+    pub fn initialize_fee_ledger(ctx: Context<InitializeFeeLedger>) -> Result<()> {
+        instructions::initialize_fee_ledger::handler(ctx)
+    }
+    pub fn update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+        instructions::update_config::handler(ctx, params)
+    }
+    pub fn set_config_paused(ctx: Context<SetConfigPaused>, paused: bool) -> Result<()> {
+        instructions::set_config_paused::handler(ctx, paused)
+    }
+    pub fn propose_config_authority(
+        ctx: Context<ProposeConfigAuthority>,
+        new_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::propose_config_authority::handler(ctx, new_authority)
+    }
+    pub fn accept_config_authority(ctx: Context<AcceptConfigAuthority>) -> Result<()> {
+        instructions::accept_config_authority::handler(ctx)
+    }
+    pub fn raise_dispute(
+        ctx: Context<RaiseDispute>,
+        amount: u64,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::raise_dispute::handler(ctx, amount, evidence_hash)
+    }
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        resolution: DisputeResolution,
+    ) -> Result<()> {
+        instructions::resolve_dispute::handler(ctx, resolution)
+    }
+    pub fn cancel_escrow<'info>(ctx: Context<'_, '_, '_, 'info, CancelEscrow<'info>>) -> Result<()> {
+        instructions::cancel_escrow::handler(ctx)
+    }
+    pub fn reclaim_expired<'info>(ctx: Context<'_, '_, '_, 'info, ReclaimExpired<'info>>) -> Result<()> {
+        instructions::reclaim_expired::handler(ctx)
+    }
+    pub fn close_stale_escrow(ctx: Context<CloseStaleEscrow>) -> Result<()> {
+        instructions::close_stale_escrow::handler(ctx)
+    }
+    pub fn pause_escrow(ctx: Context<PauseEscrow>) -> Result<()> {
+        instructions::pause_escrow::handler(ctx)
+    }
+    pub fn resume_escrow(ctx: Context<ResumeEscrow>) -> Result<()> {
+        instructions::resume_escrow::handler(ctx)
+    }
+    pub fn refund_missed_milestones(ctx: Context<RefundMissedMilestones>) -> Result<()> {
+        instructions::refund_missed_milestones::handler(ctx)
+    }
+    pub fn submit_milestone(
+        ctx: Context<SubmitMilestone>,
+        milestone_id: u32,
+        deliverable_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::submit_milestone::handler(ctx, milestone_id, deliverable_hash)
+    }
+    pub fn review_milestone(ctx: Context<ReviewMilestone>, review: MilestoneReview) -> Result<()> {
+        instructions::review_milestone::handler(ctx, review)
+    }
+    pub fn finalize_milestone(ctx: Context<FinalizeMilestone>) -> Result<()> {
+        instructions::finalize_milestone::handler(ctx)
+    }
+    pub fn migrate_config(ctx: Context<MigrateConfig>, params: ConfigParams) -> Result<()> {
+        instructions::migrate_config::handler(ctx, params)
+    }
 }
\ No newline at end of file
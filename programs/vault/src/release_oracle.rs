@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{get_return_data, invoke};
+use crate::state::*;
+// Interface for `ReleaseSchedule::Oracle`. The schedule's oracle program must
+// implement `releasable_amount(query: ReleaseQuery) -> u64`, an instruction whose
+// discriminator is `sha256("global:releasable_amount")[..8]`. It receives the
+// escrow read-only, followed by any accounts the caller forwards, and returns
+// the cumulative amount releasable so far as Borsh-encoded return data.
+//
+// Callers pass the oracle program as the first remaining account and the
+// accounts it needs after it.
+pub const RELEASABLE_AMOUNT_DISCRIMINATOR: [u8; 8] = [149, 82, 21, 196, 83, 102, 180, 84];
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReleaseQuery {
+    pub total_amount: u64,
+    pub released_amount: u64,
+    pub data: Vec<u8>,
+    // Already adjusted for pauses; see `EscrowVault::schedule_time`.
+    pub schedule_time: i64,
+}
+// `calculate_available` for every schedule, querying the oracle for `Oracle` ones.
+pub fn available<'info>(
+    escrow: &Account<'info, EscrowVault>,
+    remaining_accounts: &[AccountInfo<'info>],
+    current_time: i64,
+) -> Result<u64> {
+    let releasable = match &escrow.release_schedule {
+        ReleaseSchedule::Oracle { oracle_program, data } => {
+            let query = ReleaseQuery {
+                total_amount: escrow.total_amount,
+                released_amount: escrow.released_amount,
+                data: data.clone(),
+                schedule_time: escrow.schedule_time(current_time),
+            };
+            Some(query_releasable(oracle_program, escrow.to_account_info(), remaining_accounts, &query)?)
+        }
+        _ => None,
+    };
+    escrow.calculate_available_with(current_time, releasable)
+}
+fn query_releasable<'info>(
+    oracle_program: &Pubkey,
+    escrow: AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    query: &ReleaseQuery,
+) -> Result<u64> {
+    let (oracle, oracle_accounts) = remaining_accounts
+        .split_first()
+        .ok_or(VaultError::InvalidReleaseOracle)?;
+    require_keys_eq!(oracle.key(), *oracle_program, VaultError::InvalidReleaseOracle);
+    let mut data = RELEASABLE_AMOUNT_DISCRIMINATOR.to_vec();
+    query.serialize(&mut data)?;
+    let mut accounts = vec![AccountMeta::new_readonly(escrow.key(), false)];
+    accounts.extend(oracle_accounts.iter().map(|account| AccountMeta::new_readonly(account.key(), false)));
+    let instruction = Instruction {
+        program_id: *oracle_program,
+        accounts,
+        data,
+    };
+    let mut account_infos = vec![escrow];
+    account_infos.extend_from_slice(oracle_accounts);
+    account_infos.push(oracle.clone());
+    invoke(&instruction, &account_infos)?;
+    let (program_id, return_data) = get_return_data().ok_or(VaultError::InvalidReleaseOracle)?;
+    require_keys_eq!(program_id, *oracle_program, VaultError::InvalidReleaseOracle);
+    u64::try_from_slice(&return_data).map_err(|_| VaultError::InvalidReleaseOracle.into())
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+    #[test]
+    fn test_discriminator_matches_interface() {
+        assert_eq!(RELEASABLE_AMOUNT_DISCRIMINATOR[..], hash(b"global:releasable_amount").to_bytes()[..8]);
+    }
+}
\ No newline at end of file
@@ -1,6 +1,18 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+pub const ESCROW_VAULT_VERSION: u8 = 4;
+pub const ESCROW_CONFIG_VERSION: u8 = 2;
+pub const MAX_RELEASE_SIGNERS: usize = 5;
+pub const MAX_RELEASE_PROPOSAL_LIFETIME: i64 = 30 * 24 * 60 * 60;
+pub const MAX_ORACLE_SCHEDULE_DATA: usize = 128;
+// Steps are 16 bytes each, so twelve fit the 256 bytes reserved for the schedule.
+pub const MAX_STEP_ENTRIES: usize = 12;
+// Milestone conditions are 57 bytes each; hybrid schedules spend 33 more on their linear part.
+pub const MAX_MILESTONES: usize = 4;
+pub const MAX_HYBRID_MILESTONES: usize = 3;
 #[account]
 pub struct EscrowVault {
+    pub version: u8,                        
     pub vault_id: u64,                      
     pub owner_program: Pubkey,              
     pub owner_account: Pubkey,              
@@ -20,9 +32,12 @@ pub struct EscrowVault {
     pub updated_at: i64,                    
     pub expires_at: Option<i64>,            
     pub bump: u8,                           
+    pub paused_at: Option<i64>,
+    pub total_paused_duration: i64,
 }
 impl EscrowVault {
     pub const LEN: usize = 8 + 
+        1 +                     
         8 +                     
         32 +                    
         32 +                    
@@ -35,13 +50,40 @@ impl EscrowVault {
         8 +                     
         8 +                     
         8 +                     
-        200 +                   
-        33 +                    
+        256 +                   
+        1 + 4 + MAX_RELEASE_SIGNERS * 32 + 1 +
         2 +                     
         8 +                     
         8 +                     
         9 +                     
-        1;                      
+        1 +
+        9 +
+        8;
+    pub fn from_versioned_bytes(data: &[u8]) -> Result<(Self, u8)> {
+        require!(
+            data.len() >= 8 && data[..8] == EscrowVault::DISCRIMINATOR[..],
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        if data.len() == EscrowVaultV1::LEN {
+            let legacy = EscrowVaultV1::deserialize(&mut &data[8..])?;
+            return Ok((legacy.upgrade(), 1));
+        }
+        if data.len() > 8 && data[8] == 2 {
+            let legacy = EscrowVaultV2::deserialize(&mut &data[8..])?;
+            return Ok((legacy.upgrade(), 2));
+        }
+        if data.len() > 8 && data[8] == 3 {
+            let legacy = EscrowVaultV3::deserialize(&mut &data[8..])?;
+            return Ok((legacy.upgrade(), 3));
+        }
+        let escrow = EscrowVault::try_deserialize(&mut &data[..])?;
+        require!(
+            escrow.version <= ESCROW_VAULT_VERSION,
+            VaultError::IncompatibleVersion
+        );
+        let version = escrow.version;
+        Ok((escrow, version))
+    }
     pub fn validate_owner_program(&self, program_id: &Pubkey) -> Result<()> {
         require!(
             self.owner_program == *program_id,
@@ -61,7 +103,7 @@ impl EscrowVault {
             ReleaseAuthority::Beneficiary => Ok(signer == &self.beneficiary),
             ReleaseAuthority::Depositor => Ok(signer == &self.depositor),
             ReleaseAuthority::Either => Ok(signer == &self.beneficiary || signer == &self.depositor),
-            ReleaseAuthority::Both => Ok(false), 
+            ReleaseAuthority::Both | ReleaseAuthority::Threshold { .. } => Ok(false),
             ReleaseAuthority::Program(ref authorized) => Ok(signer == authorized),
             ReleaseAuthority::Arbiter => {
                 if let Some(ref arbiter) = self.arbiter {
@@ -72,26 +114,153 @@ impl EscrowVault {
             }
         }
     }
-    pub fn calculate_available(&self, current_time: i64) -> Result<u64> {
+    pub fn is_settled(&self) -> bool {
+        self.released_amount.saturating_add(self.refunded_amount) >= self.total_amount
+    }
+    // An inclusive fee lowers `total_amount`, which only proportional schedules follow.
+    pub fn supports_inclusive_fee(&self) -> bool {
+        !self.is_program_owned() &&
+        !matches!(
+            self.release_schedule,
+            ReleaseSchedule::Milestone { .. } | ReleaseSchedule::Hybrid { .. } | ReleaseSchedule::Steps { .. }
+        )
+    }
+    pub fn has_missed_milestones_owed(&self, current_time: i64) -> bool {
+        let schedule_time = self.schedule_time(current_time);
+        self.release_schedule.milestones().iter().any(|condition| {
+            condition.is_missed(schedule_time) && condition.missed_outcome == MissedMilestoneOutcome::PayBeneficiary
+        })
+    }
+    pub fn can_cancel(&self, signer: &Pubkey, beneficiary_consent: bool) -> bool {
+        if beneficiary_consent {
+            return signer == &self.depositor || signer == &self.beneficiary;
+        }
+        match self.release_authority {
+            ReleaseAuthority::Depositor | ReleaseAuthority::Either => signer == &self.depositor,
+            ReleaseAuthority::Program(ref authorized) => signer == authorized,
+            ReleaseAuthority::Arbiter => self.arbiter.as_ref() == Some(signer),
+            ReleaseAuthority::Beneficiary | ReleaseAuthority::Both | ReleaseAuthority::Threshold { .. } => false,
+        }
+    }
+    pub fn settle(&mut self, vested: u64, balance: u64) -> Result<(u64, u64)> {
+        let to_beneficiary = vested.min(balance);
+        let to_depositor = balance - to_beneficiary;
+        self.released_amount = self.released_amount
+            .checked_add(to_beneficiary)
+            .ok_or(VaultError::ArithmeticOverflow)?;
+        self.refunded_amount = self.refunded_amount
+            .checked_add(to_depositor)
+            .ok_or(VaultError::ArithmeticOverflow)?;
+        self.status = EscrowStatus::Cancelled;
+        Ok((to_beneficiary, to_depositor))
+    }
+    pub fn is_program_owned(&self) -> bool {
+        matches!(self.release_authority, ReleaseAuthority::Program(_))
+    }
+    pub fn is_stale(&self, current_time: i64, grace_period: i64) -> bool {
+        self.status == EscrowStatus::Initialized
+            && !self.is_program_owned()
+            && current_time >= self.created_at.saturating_add(grace_period)
+    }
+    pub fn schedule_time(&self, current_time: i64) -> i64 {
+        self.paused_at
+            .map_or(current_time, |paused_at| paused_at.min(current_time))
+            .saturating_sub(self.total_paused_duration)
+    }
+    pub fn can_pause(&self, signer: &Pubkey) -> bool {
+        match (&self.release_authority, &self.arbiter) {
+            (ReleaseAuthority::Program(authorized), _) => signer == authorized,
+            (_, Some(arbiter)) => signer == arbiter,
+            (_, None) => signer == &self.depositor,
+        }
+    }
+    pub fn pause(&mut self, current_time: i64) -> Result<()> {
         require!(
             self.status == EscrowStatus::Active || self.status == EscrowStatus::Funded,
             VaultError::InvalidStatus
         );
+        self.status = EscrowStatus::Paused;
+        self.paused_at = Some(current_time);
+        Ok(())
+    }
+    pub fn resume(&mut self, current_time: i64, fully_funded: bool) -> Result<()> {
+        self.validate_status(EscrowStatus::Paused)?;
+        let paused_at = self.paused_at.ok_or(VaultError::InvalidStatus)?;
+        let pause_duration = current_time
+            .checked_sub(paused_at)
+            .ok_or(VaultError::ArithmeticOverflow)?;
+        self.total_paused_duration = self.total_paused_duration
+            .checked_add(pause_duration)
+            .ok_or(VaultError::ArithmeticOverflow)?;
+        self.paused_at = None;
+        self.status = if fully_funded { EscrowStatus::Active } else { EscrowStatus::Funded };
+        Ok(())
+    }
+    pub fn submit_milestone(&mut self, milestone_id: u32, current_time: i64, review_window: i64) -> Result<(i64, i64)> {
+        let schedule_time = self.schedule_time(current_time);
+        let condition = self.release_schedule.open_milestone(milestone_id, schedule_time)?;
+        let review_deadline = schedule_time
+            .checked_add(review_window)
+            .ok_or(VaultError::ArithmeticOverflow)?;
+        if let Some(deadline) = condition.deadline {
+            require!(
+                review_deadline < deadline,
+                VaultError::MilestoneDeadlinePassed
+            );
+        }
+        self.release_schedule.milestone_mut(milestone_id)?.is_submitted = true;
+        Ok((schedule_time, review_deadline))
+    }
+    pub fn refund_missed_milestones(&mut self, current_time: i64) -> Result<(Vec<u32>, u64)> {
+        let schedule_time = self.schedule_time(current_time);
+        let conditions = self.release_schedule
+            .milestones_mut()
+            .ok_or(VaultError::InvalidMilestoneConfig)?;
+        let mut milestone_ids = Vec::new();
+        let mut amount = 0u64;
+        for condition in conditions.iter_mut().filter(|condition| condition.is_refundable(schedule_time)) {
+            amount = amount
+                .checked_add(condition.amount)
+                .ok_or(VaultError::ArithmeticOverflow)?;
+            condition.is_refunded = true;
+            milestone_ids.push(condition.milestone_id);
+        }
+        require!(amount > 0, VaultError::NoRefundableMilestones);
+        Ok((milestone_ids, amount))
+    }
+    pub fn release_signers(&self) -> Option<(Vec<Pubkey>, u8)> {
+        match &self.release_authority {
+            ReleaseAuthority::Both => Some((vec![self.depositor, self.beneficiary], 2)),
+            ReleaseAuthority::Threshold { signers, m } => Some((signers.clone(), *m)),
+            _ => None,
+        }
+    }
+    pub fn calculate_available(&self, current_time: i64) -> Result<u64> {
+        self.calculate_available_with(current_time, None)
+    }
+    pub fn calculate_available_with(&self, current_time: i64, oracle_releasable: Option<u64>) -> Result<u64> {
+        require!(
+            self.status == EscrowStatus::Active ||
+            self.status == EscrowStatus::Funded ||
+            self.status == EscrowStatus::Paused,
+            VaultError::InvalidStatus
+        );
         if let Some(expires_at) = self.expires_at {
             require!(
                 current_time <= expires_at,
                 VaultError::VaultExpired
             );
         }
+        let schedule_time = self.schedule_time(current_time);
         let available = match &self.release_schedule {
             ReleaseSchedule::Immediate => self.total_amount,
             ReleaseSchedule::Linear { start, end } => {
-                if current_time < *start {
+                if schedule_time < *start {
                     0
-                } else if current_time >= *end {
+                } else if schedule_time >= *end {
                     self.total_amount
                 } else {
-                    let elapsed = (current_time - start) as u128;
+                    let elapsed = (schedule_time - start) as u128;
                     let duration = (end - start) as u128;
                     let amount = (self.total_amount as u128)
                         .checked_mul(elapsed)
@@ -104,7 +273,7 @@ impl EscrowVault {
             ReleaseSchedule::Milestone { conditions } => {
                 let mut total = 0u64;
                 for condition in conditions {
-                    if condition.is_completed {
+                    if condition.is_releasable(schedule_time) {
                         total = total.checked_add(condition.amount)
                             .ok_or(VaultError::ArithmeticOverflow)?;
                     }
@@ -117,12 +286,12 @@ impl EscrowVault {
                 linear_config, 
                 milestone_config 
             } => {
-                let linear_available = if current_time < linear_config.start_time {
+                let linear_available = if schedule_time < linear_config.start_time {
                     0
-                } else if current_time >= linear_config.end_time {
+                } else if schedule_time >= linear_config.end_time {
                     *linear_portion
                 } else {
-                    let elapsed = (current_time - linear_config.start_time) as u128;
+                    let elapsed = (schedule_time - linear_config.start_time) as u128;
                     let duration = (linear_config.end_time - linear_config.start_time) as u128;
                     let base_amount = match linear_config.acceleration_type {
                         AccelerationType::Linear => {
@@ -175,7 +344,7 @@ impl EscrowVault {
                 };
                 let mut milestone_available = 0u64;
                 for condition in milestone_config {
-                    if condition.is_completed {
+                    if condition.is_releasable(schedule_time) {
                         milestone_available = milestone_available
                             .checked_add(condition.amount)
                             .ok_or(VaultError::ArithmeticOverflow)?;
@@ -184,15 +353,203 @@ impl EscrowVault {
                 linear_available.checked_add(milestone_available)
                     .ok_or(VaultError::ArithmeticOverflow)?
             },
+            ReleaseSchedule::Steps { entries } => {
+                entries
+                    .iter()
+                    .take_while(|entry| entry.timestamp <= schedule_time)
+                    .last()
+                    .map_or(0, |entry| entry.cumulative_amount)
+            },
             ReleaseSchedule::Custom { .. } => {
                 return Err(VaultError::UnsupportedSchedule.into());
             }
+            ReleaseSchedule::Oracle { .. } => {
+                oracle_releasable
+                    .ok_or(VaultError::UnsupportedSchedule)?
+                    .min(self.total_amount)
+            }
         };
         let withdrawable = available.saturating_sub(self.released_amount);
-        let effective_total = self.total_amount.saturating_sub(self.locked_amount);
+        let effective_total = self.total_amount
+            .saturating_sub(self.locked_amount)
+            .saturating_sub(self.refunded_amount);
         Ok(withdrawable.min(effective_total.saturating_sub(self.released_amount)))
     }
 }
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct EscrowVaultV1 {
+    pub vault_id: u64,                      
+    pub owner_program: Pubkey,              
+    pub owner_account: Pubkey,              
+    pub depositor: Pubkey,                  
+    pub beneficiary: Pubkey,                
+    pub arbiter: Option<Pubkey>,            
+    pub token_mint: Pubkey,                 
+    pub vault_token_account: Pubkey,        
+    pub total_amount: u64,                  
+    pub released_amount: u64,               
+    pub refunded_amount: u64,               
+    pub locked_amount: u64,                 
+    pub release_schedule: ReleaseScheduleV3,  
+    pub release_authority: ReleaseAuthority,
+    pub status: EscrowStatus,               
+    pub created_at: i64,                    
+    pub updated_at: i64,                    
+    pub expires_at: Option<i64>,            
+    pub bump: u8,                           
+}
+impl EscrowVaultV1 {
+    pub const LEN: usize = 8 + 
+        8 +                     
+        32 +                    
+        32 +                    
+        32 +                    
+        32 +                    
+        33 +                    
+        32 +                    
+        32 +                    
+        8 +                     
+        8 +                     
+        8 +                     
+        8 +                     
+        200 +                   
+        33 +                    
+        2 +                     
+        8 +                     
+        8 +                     
+        9 +                     
+        1;
+    pub fn upgrade(self) -> EscrowVault {
+        EscrowVault {
+            version: ESCROW_VAULT_VERSION,
+            vault_id: self.vault_id,
+            owner_program: self.owner_program,
+            owner_account: self.owner_account,
+            depositor: self.depositor,
+            beneficiary: self.beneficiary,
+            arbiter: self.arbiter,
+            token_mint: self.token_mint,
+            vault_token_account: self.vault_token_account,
+            total_amount: self.total_amount,
+            released_amount: self.released_amount,
+            refunded_amount: self.refunded_amount,
+            locked_amount: self.locked_amount,
+            release_schedule: self.release_schedule.upgrade(),
+            release_authority: self.release_authority,
+            status: self.status,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            expires_at: self.expires_at,
+            bump: self.bump,
+            paused_at: None,
+            total_paused_duration: 0,
+        }
+    }
+}
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct EscrowVaultV2 {
+    pub version: u8,
+    pub vault_id: u64,
+    pub owner_program: Pubkey,
+    pub owner_account: Pubkey,
+    pub depositor: Pubkey,
+    pub beneficiary: Pubkey,
+    pub arbiter: Option<Pubkey>,
+    pub token_mint: Pubkey,
+    pub vault_token_account: Pubkey,
+    pub total_amount: u64,
+    pub released_amount: u64,
+    pub refunded_amount: u64,
+    pub locked_amount: u64,
+    pub release_schedule: ReleaseScheduleV3,
+    pub release_authority: ReleaseAuthority,
+    pub status: EscrowStatus,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub expires_at: Option<i64>,
+    pub bump: u8,
+}
+impl EscrowVaultV2 {
+    pub fn upgrade(self) -> EscrowVault {
+        EscrowVault {
+            version: ESCROW_VAULT_VERSION,
+            vault_id: self.vault_id,
+            owner_program: self.owner_program,
+            owner_account: self.owner_account,
+            depositor: self.depositor,
+            beneficiary: self.beneficiary,
+            arbiter: self.arbiter,
+            token_mint: self.token_mint,
+            vault_token_account: self.vault_token_account,
+            total_amount: self.total_amount,
+            released_amount: self.released_amount,
+            refunded_amount: self.refunded_amount,
+            locked_amount: self.locked_amount,
+            release_schedule: self.release_schedule.upgrade(),
+            release_authority: self.release_authority,
+            status: self.status,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            expires_at: self.expires_at,
+            bump: self.bump,
+            paused_at: None,
+            total_paused_duration: 0,
+        }
+    }
+}
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct EscrowVaultV3 {
+    pub version: u8,
+    pub vault_id: u64,
+    pub owner_program: Pubkey,
+    pub owner_account: Pubkey,
+    pub depositor: Pubkey,
+    pub beneficiary: Pubkey,
+    pub arbiter: Option<Pubkey>,
+    pub token_mint: Pubkey,
+    pub vault_token_account: Pubkey,
+    pub total_amount: u64,
+    pub released_amount: u64,
+    pub refunded_amount: u64,
+    pub locked_amount: u64,
+    pub release_schedule: ReleaseScheduleV3,
+    pub release_authority: ReleaseAuthority,
+    pub status: EscrowStatus,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub expires_at: Option<i64>,
+    pub bump: u8,
+    pub paused_at: Option<i64>,
+    pub total_paused_duration: i64,
+}
+impl EscrowVaultV3 {
+    pub fn upgrade(self) -> EscrowVault {
+        EscrowVault {
+            version: ESCROW_VAULT_VERSION,
+            vault_id: self.vault_id,
+            owner_program: self.owner_program,
+            owner_account: self.owner_account,
+            depositor: self.depositor,
+            beneficiary: self.beneficiary,
+            arbiter: self.arbiter,
+            token_mint: self.token_mint,
+            vault_token_account: self.vault_token_account,
+            total_amount: self.total_amount,
+            released_amount: self.released_amount,
+            refunded_amount: self.refunded_amount,
+            locked_amount: self.locked_amount,
+            release_schedule: self.release_schedule.upgrade(),
+            release_authority: self.release_authority,
+            status: self.status,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            expires_at: self.expires_at,
+            bump: self.bump,
+            paused_at: self.paused_at,
+            total_paused_duration: self.total_paused_duration,
+        }
+    }
+}
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub enum ReleaseSchedule {
     Immediate,                              
@@ -212,6 +569,90 @@ pub enum ReleaseSchedule {
     Custom { 
         data: Vec<u8> 
     },                                      
+    Steps {
+        entries: Vec<StepEntry>,
+    },
+    Oracle {
+        oracle_program: Pubkey,
+        data: Vec<u8>,
+    },
+}
+impl ReleaseSchedule {
+    pub fn milestones_mut(&mut self) -> Option<&mut Vec<MilestoneCondition>> {
+        match self {
+            ReleaseSchedule::Milestone { conditions } => Some(conditions),
+            ReleaseSchedule::Hybrid { milestone_config, .. } => Some(milestone_config),
+            _ => None,
+        }
+    }
+    pub fn open_milestone(&self, milestone_id: u32, schedule_time: i64) -> Result<&MilestoneCondition> {
+        let condition = self
+            .milestones()
+            .iter()
+            .find(|condition| condition.milestone_id == milestone_id)
+            .ok_or(VaultError::MilestoneNotFound)?;
+        require!(!condition.is_completed, VaultError::MilestoneAlreadyCompleted);
+        require!(!condition.is_missed(schedule_time), VaultError::MilestoneDeadlinePassed);
+        Ok(condition)
+    }
+    pub fn complete_milestone(&mut self, milestone_id: u32, schedule_time: i64) -> Result<()> {
+        self.open_milestone(milestone_id, schedule_time)?;
+        self.milestone_mut(milestone_id)?.is_completed = true;
+        Ok(())
+    }
+    pub fn milestone_mut(&mut self, milestone_id: u32) -> Result<&mut MilestoneCondition> {
+        self.milestones_mut()
+            .and_then(|conditions| conditions.iter_mut().find(|condition| condition.milestone_id == milestone_id))
+            .ok_or_else(|| VaultError::MilestoneNotFound.into())
+    }
+    pub fn milestones(&self) -> &[MilestoneCondition] {
+        match self {
+            ReleaseSchedule::Milestone { conditions } => conditions,
+            ReleaseSchedule::Hybrid { milestone_config, .. } => milestone_config,
+            _ => &[],
+        }
+    }
+    pub fn validate_milestone_deadlines(conditions: &[MilestoneCondition], current_time: i64) -> Result<()> {
+        for condition in conditions {
+            if let (Some(deadline), false, false) = (condition.deadline, condition.is_completed, condition.is_refunded) {
+                require!(
+                    deadline > current_time,
+                    VaultError::InvalidMilestoneConfig
+                );
+            }
+        }
+        Ok(())
+    }
+    pub fn validate_steps(entries: &[StepEntry], total_amount: u64, max_duration: i64) -> Result<()> {
+        require!(
+            !entries.is_empty() && entries.len() <= MAX_STEP_ENTRIES,
+            VaultError::InvalidStepSchedule
+        );
+        for pair in entries.windows(2) {
+            require!(
+                pair[0].timestamp < pair[1].timestamp && pair[0].cumulative_amount < pair[1].cumulative_amount,
+                VaultError::InvalidStepSchedule
+            );
+        }
+        let (first, last) = (entries[0], entries[entries.len() - 1]);
+        require!(
+            last.cumulative_amount == total_amount,
+            VaultError::InvalidStepSchedule
+        );
+        let duration = last.timestamp
+            .checked_sub(first.timestamp)
+            .ok_or(VaultError::InvalidTimeRange)?;
+        require!(
+            duration <= max_duration,
+            VaultError::InvalidTimeRange
+        );
+        Ok(())
+    }
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct StepEntry {
+    pub timestamp: i64,
+    pub cumulative_amount: u64,
 }
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct MilestoneCondition {
@@ -219,6 +660,103 @@ pub struct MilestoneCondition {
     pub amount: u64,
     pub required_approval: Pubkey,
     pub is_completed: bool,
+    pub deadline: Option<i64>,
+    pub missed_outcome: MissedMilestoneOutcome,
+    pub is_refunded: bool,
+    pub is_submitted: bool,
+}
+impl MilestoneCondition {
+    pub fn is_missed(&self, schedule_time: i64) -> bool {
+        !self.is_completed && self.deadline.is_some_and(|deadline| schedule_time > deadline)
+    }
+    pub fn is_releasable(&self, schedule_time: i64) -> bool {
+        self.is_completed ||
+            (self.is_missed(schedule_time) && self.missed_outcome == MissedMilestoneOutcome::PayBeneficiary)
+    }
+    pub fn is_refundable(&self, schedule_time: i64) -> bool {
+        !self.is_refunded &&
+            !self.is_submitted &&
+            self.is_missed(schedule_time) &&
+            self.missed_outcome == MissedMilestoneOutcome::RefundDepositor
+    }
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum MissedMilestoneOutcome {
+    RefundDepositor,
+    PayBeneficiary,
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum ReleaseScheduleV3 {
+    Immediate,
+    Linear {
+        start: i64,
+        end: i64,
+    },
+    Milestone {
+        conditions: Vec<MilestoneConditionV3>,
+    },
+    Hybrid {
+        linear_portion: u64,
+        milestone_portion: u64,
+        linear_config: LinearConfig,
+        milestone_config: Vec<MilestoneConditionV3>,
+    },
+    Custom {
+        data: Vec<u8>,
+    },
+    Steps {
+        entries: Vec<StepEntry>,
+    },
+    Oracle {
+        oracle_program: Pubkey,
+        data: Vec<u8>,
+    },
+}
+impl ReleaseScheduleV3 {
+    pub fn upgrade(self) -> ReleaseSchedule {
+        let upgrade_all = |conditions: Vec<MilestoneConditionV3>| {
+            conditions.into_iter().map(MilestoneConditionV3::upgrade).collect()
+        };
+        match self {
+            ReleaseScheduleV3::Immediate => ReleaseSchedule::Immediate,
+            ReleaseScheduleV3::Linear { start, end } => ReleaseSchedule::Linear { start, end },
+            ReleaseScheduleV3::Milestone { conditions } => ReleaseSchedule::Milestone {
+                conditions: upgrade_all(conditions),
+            },
+            ReleaseScheduleV3::Hybrid { linear_portion, milestone_portion, linear_config, milestone_config } => {
+                ReleaseSchedule::Hybrid {
+                    linear_portion,
+                    milestone_portion,
+                    linear_config,
+                    milestone_config: upgrade_all(milestone_config),
+                }
+            },
+            ReleaseScheduleV3::Custom { data } => ReleaseSchedule::Custom { data },
+            ReleaseScheduleV3::Steps { entries } => ReleaseSchedule::Steps { entries },
+            ReleaseScheduleV3::Oracle { oracle_program, data } => ReleaseSchedule::Oracle { oracle_program, data },
+        }
+    }
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MilestoneConditionV3 {
+    pub milestone_id: u32,
+    pub amount: u64,
+    pub required_approval: Pubkey,
+    pub is_completed: bool,
+}
+impl MilestoneConditionV3 {
+    pub fn upgrade(self) -> MilestoneCondition {
+        MilestoneCondition {
+            milestone_id: self.milestone_id,
+            amount: self.amount,
+            required_approval: self.required_approval,
+            is_completed: self.is_completed,
+            deadline: None,
+            missed_outcome: MissedMilestoneOutcome::RefundDepositor,
+            is_refunded: false,
+            is_submitted: false,
+        }
+    }
 }
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct LinearConfig {
@@ -240,8 +778,29 @@ pub enum ReleaseAuthority {
     Both,                                   
     Program(Pubkey),                        
     Arbiter,                                
+    Threshold {
+        signers: Vec<Pubkey>,
+        m: u8,
+    },
 }
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+impl ReleaseAuthority {
+    pub fn validate(&self) -> Result<()> {
+        if let ReleaseAuthority::Threshold { signers, m } = self {
+            require!(
+                *m > 0 && *m as usize <= signers.len() && signers.len() <= MAX_RELEASE_SIGNERS,
+                VaultError::InvalidReleaseAuthority
+            );
+            for (i, signer) in signers.iter().enumerate() {
+                require!(
+                    !signers[..i].contains(signer),
+                    VaultError::InvalidReleaseAuthority
+                );
+            }
+        }
+        Ok(())
+    }
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 pub enum EscrowStatus {
     Initialized,                            
     Funded,                                 
@@ -253,25 +812,70 @@ pub enum EscrowStatus {
 }
 #[account]
 pub struct EscrowConfig {
+    pub layout_version: u8,
     pub authority: Pubkey,                  
+    pub pending_authority: Option<Pubkey>,
     pub fee_basis_points: u16,              
     pub fee_recipient: Pubkey,              
+    pub fee_mode: FeeMode,
     pub min_escrow_amount: u64,             
     pub max_escrow_duration: i64,           
+    pub expiry_policy: ExpiryPolicy,
+    pub unfunded_grace_period: i64,
+    pub milestone_review_window: i64,
     pub paused: bool,                       
     pub version: u32,                       
     pub bump: u8,
 }
 impl EscrowConfig {
     pub const LEN: usize = 8 + 
+        1 +
         32 +                    
+        33 +
         2 +                     
         32 +                    
+        1 +
         8 +                     
         8 +                     
+        1 +
+        8 +
+        8 +
         1 +                     
         4 +                     
         1;                      
+    pub fn calculate_fee(&self, amount: u64) -> Result<u64> {
+        let fee = (amount as u128)
+            .checked_mul(self.fee_basis_points as u128)
+            .ok_or(VaultError::ArithmeticOverflow)?
+            .checked_div(10000)
+            .ok_or(VaultError::ArithmeticOverflow)?;
+        Ok(fee as u64)
+    }
+    pub fn split_deposit(&self, amount: u64) -> Result<(u64, u64)> {
+        let fee_amount = self.calculate_fee(amount)?;
+        let deposit_amount = match self.fee_mode {
+            FeeMode::OnTop => amount,
+            FeeMode::Inclusive => amount
+                .checked_sub(fee_amount)
+                .ok_or(VaultError::ArithmeticOverflow)?,
+        };
+        Ok((deposit_amount, fee_amount))
+    }
+    pub fn apply(&mut self, params: &ConfigParams) {
+        self.fee_basis_points = params.fee_basis_points;
+        self.fee_mode = params.fee_mode;
+        self.min_escrow_amount = params.min_escrow_amount;
+        self.max_escrow_duration = params.max_escrow_duration;
+        self.expiry_policy = params.expiry_policy;
+        self.unfunded_grace_period = params.unfunded_grace_period;
+        self.milestone_review_window = params.milestone_review_window;
+    }
+    pub fn bump_version(&mut self) -> Result<u32> {
+        self.version = self.version
+            .checked_add(1)
+            .ok_or(VaultError::ArithmeticOverflow)?;
+        Ok(self.version)
+    }
     pub fn is_compatible(&self, required_version: u32) -> Result<()> {
         require!(
             self.version >= required_version,
@@ -280,6 +884,312 @@ impl EscrowConfig {
         Ok(())
     }
 }
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct EscrowConfigV1 {
+    pub authority: Pubkey,
+    pub fee_basis_points: u16,
+    pub fee_recipient: Pubkey,
+    pub min_escrow_amount: u64,
+    pub max_escrow_duration: i64,
+    pub paused: bool,
+    pub version: u32,
+    pub bump: u8,
+}
+impl EscrowConfigV1 {
+    pub const LEN: usize = 8 +
+        32 +
+        2 +
+        32 +
+        8 +
+        8 +
+        1 +
+        4 +
+        1;
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= 8 && data[..8] == EscrowConfig::DISCRIMINATOR[..],
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        require!(
+            data.len() == EscrowConfigV1::LEN,
+            VaultError::AlreadyMigrated
+        );
+        Ok(EscrowConfigV1::deserialize(&mut &data[8..])?)
+    }
+    pub fn upgrade(self, params: &ConfigParams) -> EscrowConfig {
+        EscrowConfig {
+            layout_version: ESCROW_CONFIG_VERSION,
+            authority: self.authority,
+            pending_authority: None,
+            fee_basis_points: params.fee_basis_points,
+            fee_recipient: self.fee_recipient,
+            fee_mode: params.fee_mode,
+            min_escrow_amount: params.min_escrow_amount,
+            max_escrow_duration: params.max_escrow_duration,
+            expiry_policy: params.expiry_policy,
+            unfunded_grace_period: params.unfunded_grace_period,
+            milestone_review_window: params.milestone_review_window,
+            paused: self.paused,
+            version: self.version,
+            bump: self.bump,
+        }
+    }
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ConfigParams {
+    pub fee_basis_points: u16,
+    pub fee_mode: FeeMode,
+    pub min_escrow_amount: u64,
+    pub max_escrow_duration: i64,
+    pub expiry_policy: ExpiryPolicy,
+    pub unfunded_grace_period: i64,
+    pub milestone_review_window: i64,
+}
+impl ConfigParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.fee_basis_points <= 10000, 
+            VaultError::InvalidAmount
+        );
+        require!(
+            self.max_escrow_duration > 0 && self.unfunded_grace_period > 0 && self.milestone_review_window > 0,
+            VaultError::InvalidTimeRange
+        );
+        Ok(())
+    }
+}
+#[account]
+pub struct ReleaseProposal {
+    pub escrow_vault: Pubkey,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub amount: u64,
+    pub approvals: Vec<Pubkey>,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+impl ReleaseProposal {
+    pub const LEN: usize = 8 +
+        32 +
+        8 +
+        32 +
+        8 +
+        4 + MAX_RELEASE_SIGNERS * 32 +
+        8 +
+        8 +
+        1;
+    pub fn approve(&mut self, signer: Pubkey) -> Result<()> {
+        require!(
+            !self.approvals.contains(&signer),
+            VaultError::AlreadyApproved
+        );
+        self.approvals.push(signer);
+        Ok(())
+    }
+    pub fn approval_count(&self, signers: &[Pubkey]) -> usize {
+        self.approvals.iter().filter(|approver| signers.contains(approver)).count()
+    }
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        current_time > self.expires_at
+    }
+}
+#[account]
+pub struct Dispute {
+    pub escrow_vault: Pubkey,
+    pub raised_by: Pubkey,
+    pub amount: u64,
+    pub evidence_hash: [u8; 32],
+    pub previous_status: EscrowStatus,
+    pub raised_at: i64,
+    pub bump: u8,
+}
+impl Dispute {
+    pub const LEN: usize = 8 +
+        32 +
+        32 +
+        8 +
+        32 +
+        1 +
+        8 +
+        1;
+}
+#[account]
+pub struct MilestoneSubmission {
+    pub escrow_vault: Pubkey,
+    pub milestone_id: u32,
+    pub submitter: Pubkey,
+    pub deliverable_hash: [u8; 32],
+    // Both in schedule time, so pauses hold the review window open.
+    pub submitted_at: i64,
+    pub review_deadline: i64,
+    pub bump: u8,
+}
+impl MilestoneSubmission {
+    pub const LEN: usize = 8 +
+        32 +
+        4 +
+        32 +
+        32 +
+        8 +
+        8 +
+        1;
+    pub fn review_elapsed(&self, current_time: i64) -> bool {
+        current_time > self.review_deadline
+    }
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum MilestoneReview {
+    Approve,
+    Reject {
+        reason_hash: [u8; 32],
+    },
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum DisputeResolution {
+    Amounts {
+        to_beneficiary: u64,
+        to_depositor: u64,
+    },
+    BasisPoints {
+        beneficiary_bps: u16,
+    },
+}
+impl DisputeResolution {
+    pub fn split(&self, locked: u64) -> Result<(u64, u64)> {
+        match *self {
+            DisputeResolution::Amounts { to_beneficiary, to_depositor } => {
+                let total = to_beneficiary
+                    .checked_add(to_depositor)
+                    .ok_or(VaultError::ArithmeticOverflow)?;
+                require!(total == locked, VaultError::InvalidDisputeResolution);
+                Ok((to_beneficiary, to_depositor))
+            },
+            DisputeResolution::BasisPoints { beneficiary_bps } => {
+                require!(beneficiary_bps <= 10000, VaultError::InvalidDisputeResolution);
+                let to_beneficiary = (locked as u128)
+                    .checked_mul(beneficiary_bps as u128)
+                    .ok_or(VaultError::ArithmeticOverflow)?
+                    .checked_div(10000)
+                    .ok_or(VaultError::ArithmeticOverflow)? as u64;
+                Ok((to_beneficiary, locked - to_beneficiary))
+            },
+        }
+    }
+}
+#[event]
+pub struct DisputeRaised {
+    pub escrow_vault: Pubkey,
+    pub raised_by: Pubkey,
+    pub amount: u64,
+    pub evidence_hash: [u8; 32],
+}
+#[event]
+pub struct DisputeResolved {
+    pub escrow_vault: Pubkey,
+    pub arbiter: Pubkey,
+    pub to_beneficiary: u64,
+    pub to_depositor: u64,
+    pub status: EscrowStatus,
+}
+#[event]
+pub struct EscrowCancelled {
+    pub escrow_vault: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub to_beneficiary: u64,
+    pub to_depositor: u64,
+}
+#[event]
+pub struct EscrowReclaimed {
+    pub escrow_vault: Pubkey,
+    pub expiry_policy: ExpiryPolicy,
+    pub to_beneficiary: u64,
+    pub to_depositor: u64,
+}
+#[event]
+pub struct EscrowPaused {
+    pub escrow_vault: Pubkey,
+    pub paused_by: Pubkey,
+    pub paused_at: i64,
+}
+#[event]
+pub struct EscrowResumed {
+    pub escrow_vault: Pubkey,
+    pub resumed_by: Pubkey,
+    pub total_paused_duration: i64,
+}
+#[event]
+pub struct MilestoneSubmitted {
+    pub escrow_vault: Pubkey,
+    pub milestone_id: u32,
+    pub deliverable_hash: [u8; 32],
+    pub review_deadline: i64,
+}
+#[event]
+pub struct MilestoneReviewed {
+    pub escrow_vault: Pubkey,
+    pub milestone_id: u32,
+    pub deliverable_hash: [u8; 32],
+    pub review: MilestoneReview,
+    pub finalized_by_timeout: bool,
+}
+#[event]
+pub struct MissedMilestonesRefunded {
+    pub escrow_vault: Pubkey,
+    pub milestone_ids: Vec<u32>,
+    pub amount: u64,
+}
+#[event]
+pub struct ConfigUpdated {
+    pub fee_basis_points: u16,
+    pub fee_mode: FeeMode,
+    pub fee_recipient: Pubkey,
+    pub min_escrow_amount: u64,
+    pub max_escrow_duration: i64,
+    pub expiry_policy: ExpiryPolicy,
+    pub unfunded_grace_period: i64,
+    pub milestone_review_window: i64,
+    pub version: u32,
+}
+#[event]
+pub struct ConfigPausedChanged {
+    pub paused: bool,
+    pub version: u32,
+}
+#[event]
+pub struct AuthorityTransferProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+    pub version: u32,
+}
+#[event]
+pub struct AuthorityTransferred {
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+    pub version: u32,
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum FeeMode {
+    OnTop,
+    Inclusive,
+}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ExpiryPolicy {
+    RefundAll,
+    PayVested,
+}
+#[account]
+pub struct FeeLedger {
+    pub mint: Pubkey,
+    pub total_collected: u64,
+    pub bump: u8,
+}
+impl FeeLedger {
+    pub const LEN: usize = 8 +
+        32 +
+        8 +
+        1;
+}
 #[error_code]
 pub enum VaultError {
     #[msg("Unauthorized program attempting to access vault")]
@@ -314,4 +1224,565 @@ pub enum VaultError {
     MilestoneAlreadyCompleted,
     #[msg("Invalid milestone configuration")]
     InvalidMilestoneConfig,
+    #[msg("Account is already on the current layout")]
+    AlreadyMigrated,
+    #[msg("Invalid release authority configuration")]
+    InvalidReleaseAuthority,
+    #[msg("Release proposal has expired")]
+    ProposalExpired,
+    #[msg("Signer has already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Not enough approvals to execute the release")]
+    InsufficientApprovals,
+    #[msg("Fee token account and fee ledger are required when a fee is charged")]
+    FeeAccountsRequired,
+    #[msg("No authority transfer is pending for this signer")]
+    NoPendingAuthority,
+    #[msg("Escrow has no arbiter to resolve disputes")]
+    NoArbiter,
+    #[msg("Dispute resolution does not account for the locked amount")]
+    InvalidDisputeResolution,
+    #[msg("Cancelling this escrow requires the beneficiary's consent")]
+    BeneficiaryConsentRequired,
+    #[msg("Escrow has not expired")]
+    NotExpired,
+    #[msg("Unfunded escrow grace period has not elapsed")]
+    GracePeriodNotElapsed,
+    #[msg("Release oracle is missing, mismatched or returned an invalid amount")]
+    InvalidReleaseOracle,
+    #[msg("Step schedule must increase in time and amount and end at the escrow total")]
+    InvalidStepSchedule,
+    #[msg("Milestone deadline has passed")]
+    MilestoneDeadlinePassed,
+    #[msg("No missed milestones are refundable")]
+    NoRefundableMilestones,
+    #[msg("Missed milestones owed to the beneficiary have not been withdrawn")]
+    MissedMilestonesUnclaimed,
+    #[msg("Milestone review window has not elapsed")]
+    ReviewWindowNotElapsed,
+    #[msg("Inclusive fees are not supported for this escrow")]
+    InclusiveFeeUnsupported,
+    #[msg("Escrow is managed by the program that owns it")]
+    ProgramOwnedEscrow,
+    #[msg("Milestone has a submission awaiting review")]
+    MilestoneSubmissionPending,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn legacy_escrow() -> EscrowVaultV1 {
+        EscrowVaultV1 {
+            vault_id: 9,
+            owner_program: Pubkey::new_unique(),
+            owner_account: Pubkey::new_unique(),
+            depositor: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+            arbiter: None,
+            token_mint: Pubkey::new_unique(),
+            vault_token_account: Pubkey::new_unique(),
+            total_amount: 1_000,
+            released_amount: 250,
+            refunded_amount: 0,
+            locked_amount: 0,
+            release_schedule: ReleaseScheduleV3::Linear { start: 100, end: 200 },
+            release_authority: ReleaseAuthority::Beneficiary,
+            status: EscrowStatus::Active,
+            created_at: 50,
+            updated_at: 150,
+            expires_at: None,
+            bump: 253,
+        }
+    }
+    #[test]
+    fn test_migrates_v1_escrow_bytes() {
+        let legacy = legacy_escrow();
+        let mut data = EscrowVault::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+        data.resize(EscrowVaultV1::LEN, 0);
+        let (escrow, from_version) = EscrowVault::from_versioned_bytes(&data).unwrap();
+        assert_eq!(from_version, 1);
+        assert_eq!(escrow.version, ESCROW_VAULT_VERSION);
+        assert_eq!(escrow.vault_id, 9);
+        assert_eq!(escrow.owner_account, legacy.owner_account);
+        assert_eq!(escrow.released_amount, 250);
+        assert!(escrow.status == EscrowStatus::Active);
+        assert_eq!(escrow.calculate_available(150).unwrap(), 250);
+        let mut migrated = vec![0u8; EscrowVault::LEN];
+        escrow.try_serialize(&mut &mut migrated[..]).unwrap();
+        let (escrow, from_version) = EscrowVault::from_versioned_bytes(&migrated).unwrap();
+        assert_eq!(from_version, ESCROW_VAULT_VERSION);
+        assert_eq!(escrow.bump, 253);
+        assert!(EscrowVault::try_deserialize(&mut &migrated[..]).is_ok());
+    }
+    #[test]
+    fn test_rejects_unknown_escrow_version() {
+        let mut escrow = legacy_escrow().upgrade();
+        escrow.version = ESCROW_VAULT_VERSION + 1;
+        let mut data = vec![0u8; EscrowVault::LEN];
+        escrow.try_serialize(&mut &mut data[..]).unwrap();
+        assert!(EscrowVault::from_versioned_bytes(&data).is_err());
+    }
+    #[test]
+    fn test_release_signers_follow_authority() {
+        let mut escrow = legacy_escrow().upgrade();
+        assert!(escrow.release_signers().is_none());
+        escrow.release_authority = ReleaseAuthority::Both;
+        assert_eq!(escrow.release_signers(), Some((vec![escrow.depositor, escrow.beneficiary], 2)));
+        assert!(!escrow.can_withdraw(&escrow.beneficiary).unwrap());
+        let signers = vec![Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        escrow.release_authority = ReleaseAuthority::Threshold { signers: signers.clone(), m: 2 };
+        assert!(escrow.release_authority.validate().is_ok());
+        assert_eq!(escrow.release_signers(), Some((signers.clone(), 2)));
+        for (signers, m) in [(signers.clone(), 0), (signers.clone(), 4), (vec![signers[0], signers[0]], 1)] {
+            assert!(ReleaseAuthority::Threshold { signers, m }.validate().is_err());
+        }
+    }
+    #[test]
+    fn test_release_proposal_counts_current_signers() {
+        let signers = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut proposal = ReleaseProposal {
+            escrow_vault: Pubkey::new_unique(),
+            proposal_id: 0,
+            proposer: signers[0],
+            amount: 100,
+            approvals: vec![signers[0]],
+            created_at: 0,
+            expires_at: 10,
+            bump: 255,
+        };
+        assert!(proposal.approve(signers[0]).is_err());
+        proposal.approve(Pubkey::new_unique()).unwrap();
+        assert_eq!(proposal.approval_count(&signers), 1);
+        proposal.approve(signers[1]).unwrap();
+        assert_eq!(proposal.approval_count(&signers), 2);
+        assert!(!proposal.is_expired(10));
+        assert!(proposal.is_expired(11));
+    }
+    fn test_config() -> EscrowConfig {
+        EscrowConfig {
+            layout_version: ESCROW_CONFIG_VERSION,
+            authority: Pubkey::new_unique(),
+            pending_authority: None,
+            fee_basis_points: 250,
+            fee_recipient: Pubkey::new_unique(),
+            fee_mode: FeeMode::OnTop,
+            min_escrow_amount: 0,
+            max_escrow_duration: 1,
+            expiry_policy: ExpiryPolicy::RefundAll,
+            unfunded_grace_period: 1,
+            milestone_review_window: 1,
+            paused: false,
+            version: 1,
+            bump: 255,
+        }
+    }
+    #[test]
+    fn test_fee_rounds_down_in_basis_points() {
+        let mut config = test_config();
+        assert_eq!(config.calculate_fee(1_000_000).unwrap(), 25_000);
+        assert_eq!(config.calculate_fee(39).unwrap(), 0);
+        config.fee_basis_points = 0;
+        assert_eq!(config.calculate_fee(u64::MAX).unwrap(), 0);
+    }
+    #[test]
+    fn test_config_version_bumps_on_every_change() {
+        let mut config = test_config();
+        assert_eq!(config.bump_version().unwrap(), 2);
+        assert_eq!(config.bump_version().unwrap(), 3);
+        assert!(config.is_compatible(3).is_ok());
+        config.version = u32::MAX;
+        assert!(config.bump_version().is_err());
+    }
+    #[test]
+    fn test_migrates_v1_config_bytes() {
+        let legacy = EscrowConfigV1 {
+            authority: Pubkey::new_unique(),
+            fee_basis_points: 250,
+            fee_recipient: Pubkey::new_unique(),
+            min_escrow_amount: 1,
+            max_escrow_duration: 100,
+            paused: true,
+            version: 7,
+            bump: 254,
+        };
+        let mut data = EscrowConfig::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+        assert_eq!(data.len(), EscrowConfigV1::LEN);
+        let params = ConfigParams {
+            fee_basis_points: 100,
+            fee_mode: FeeMode::OnTop,
+            min_escrow_amount: 5,
+            max_escrow_duration: 10,
+            expiry_policy: ExpiryPolicy::PayVested,
+            unfunded_grace_period: 20,
+            milestone_review_window: 30,
+        };
+        let config = EscrowConfigV1::from_bytes(&data).unwrap().upgrade(&params);
+        assert_eq!(config.layout_version, ESCROW_CONFIG_VERSION);
+        assert_eq!((config.authority, config.fee_recipient), (legacy.authority, legacy.fee_recipient));
+        assert_eq!((config.paused, config.version, config.bump), (true, 7, 254));
+        assert_eq!((config.fee_basis_points, config.milestone_review_window), (100, 30));
+        let mut migrated = vec![0u8; EscrowConfig::LEN];
+        config.try_serialize(&mut &mut migrated[..]).unwrap();
+        assert!(EscrowConfig::try_deserialize(&mut &migrated[..]).is_ok());
+        assert!(EscrowConfigV1::from_bytes(&migrated).is_err());
+        assert!(EscrowConfig::try_deserialize(&mut &data[..]).is_err());
+    }
+    #[test]
+    fn test_config_params_validate_and_apply() {
+        let params = ConfigParams {
+            fee_basis_points: 100,
+            fee_mode: FeeMode::Inclusive,
+            min_escrow_amount: 5,
+            max_escrow_duration: 10,
+            expiry_policy: ExpiryPolicy::PayVested,
+            unfunded_grace_period: 2,
+            milestone_review_window: 3,
+        };
+        assert!(params.validate().is_ok());
+        assert!(ConfigParams { fee_basis_points: 10_001, ..params }.validate().is_err());
+        assert!(ConfigParams { milestone_review_window: 0, ..params }.validate().is_err());
+        let mut config = test_config();
+        config.apply(&params);
+        assert_eq!((config.fee_basis_points, config.fee_mode), (100, FeeMode::Inclusive));
+        assert_eq!((config.max_escrow_duration, config.milestone_review_window), (10, 3));
+    }
+    #[test]
+    fn test_dispute_resolution_splits_locked_amount() {
+        let amounts = DisputeResolution::Amounts { to_beneficiary: 300, to_depositor: 700 };
+        assert_eq!(amounts.split(1_000).unwrap(), (300, 700));
+        assert!(amounts.split(999).is_err());
+        assert_eq!(DisputeResolution::BasisPoints { beneficiary_bps: 2_500 }.split(999).unwrap(), (249, 750));
+        assert_eq!(DisputeResolution::BasisPoints { beneficiary_bps: 10_000 }.split(999).unwrap(), (999, 0));
+        assert!(DisputeResolution::BasisPoints { beneficiary_bps: 10_001 }.split(999).is_err());
+    }
+    #[test]
+    fn test_locked_and_refunded_funds_are_not_available() {
+        let mut escrow = legacy_escrow().upgrade();
+        escrow.released_amount = 0;
+        escrow.release_schedule = ReleaseSchedule::Immediate;
+        escrow.locked_amount = 400;
+        assert_eq!(escrow.calculate_available(150).unwrap(), 600);
+        escrow.locked_amount = 0;
+        escrow.released_amount = 100;
+        escrow.refunded_amount = 300;
+        assert_eq!(escrow.calculate_available(150).unwrap(), 600);
+    }
+    #[test]
+    fn test_cancel_rules_follow_authority_and_consent() {
+        let mut escrow = legacy_escrow().upgrade();
+        let (depositor, beneficiary, outsider) = (escrow.depositor, escrow.beneficiary, Pubkey::new_unique());
+        assert!(!escrow.can_cancel(&depositor, false));
+        assert!(escrow.can_cancel(&depositor, true));
+        assert!(escrow.can_cancel(&beneficiary, true));
+        assert!(!escrow.can_cancel(&outsider, true));
+        escrow.release_authority = ReleaseAuthority::Either;
+        assert!(escrow.can_cancel(&depositor, false));
+        assert!(!escrow.can_cancel(&beneficiary, false));
+        escrow.release_authority = ReleaseAuthority::Arbiter;
+        escrow.arbiter = Some(outsider);
+        assert!(escrow.can_cancel(&outsider, false));
+        assert!(!escrow.can_cancel(&depositor, false));
+    }
+    #[test]
+    fn test_only_unfunded_escrows_go_stale() {
+        let mut escrow = legacy_escrow().upgrade();
+        assert!(!escrow.is_stale(i64::MAX, 0));
+        escrow.status = EscrowStatus::Initialized;
+        assert!(!escrow.is_stale(149, 100));
+        assert!(escrow.is_stale(150, 100));
+        assert!(!escrow.is_stale(150, i64::MAX));
+        escrow.release_authority = ReleaseAuthority::Program(Pubkey::new_unique());
+        assert!(!escrow.is_stale(i64::MAX, 0));
+    }
+    #[test]
+    fn test_migrates_v2_escrow_bytes() {
+        let escrow = legacy_escrow().upgrade();
+        let mut data = vec![0u8; EscrowVault::LEN];
+        escrow.try_serialize(&mut &mut data[..]).unwrap();
+        data[8] = 2;
+        let (migrated, from_version) = EscrowVault::from_versioned_bytes(&data).unwrap();
+        assert_eq!(from_version, 2);
+        assert_eq!(migrated.version, ESCROW_VAULT_VERSION);
+        assert_eq!((migrated.paused_at, migrated.total_paused_duration), (None, 0));
+        assert_eq!(migrated.bump, 253);
+    }
+    #[test]
+    fn test_migrates_v3_milestones_without_deadlines() {
+        let mut legacy = legacy_escrow();
+        legacy.release_schedule = ReleaseScheduleV3::Milestone {
+            conditions: vec![MilestoneConditionV3 {
+                milestone_id: 0,
+                amount: 1_000,
+                required_approval: Pubkey::new_unique(),
+                is_completed: true,
+            }],
+        };
+        let mut data = EscrowVault::DISCRIMINATOR.to_vec();
+        data.push(3);
+        legacy.serialize(&mut data).unwrap();
+        Some(120i64).serialize(&mut data).unwrap();
+        30i64.serialize(&mut data).unwrap();
+        let (migrated, from_version) = EscrowVault::from_versioned_bytes(&data).unwrap();
+        assert_eq!(from_version, 3);
+        assert_eq!((migrated.paused_at, migrated.total_paused_duration), (Some(120), 30));
+        let condition = &migrated.release_schedule.milestones()[0];
+        assert!(condition.is_completed && condition.deadline.is_none() && !condition.is_refunded);
+    }
+    #[test]
+    fn test_pause_shifts_linear_window() {
+        let mut escrow = legacy_escrow().upgrade();
+        escrow.released_amount = 0;
+        escrow.pause(150).unwrap();
+        assert!(escrow.pause(150).is_err());
+        assert_eq!(escrow.calculate_available(180).unwrap(), 500);
+        escrow.resume(170, true).unwrap();
+        assert!(escrow.status == EscrowStatus::Active);
+        assert_eq!(escrow.calculate_available(180).unwrap(), 600);
+        assert_eq!(escrow.calculate_available(219).unwrap(), 990);
+        assert_eq!(escrow.calculate_available(220).unwrap(), 1_000);
+        escrow.arbiter = Some(Pubkey::new_unique());
+        assert!(!escrow.can_pause(&escrow.depositor));
+        let program = Pubkey::new_unique();
+        escrow.release_authority = ReleaseAuthority::Program(program);
+        assert!(escrow.can_pause(&program));
+        assert!(!escrow.can_pause(&escrow.arbiter.unwrap()) && !escrow.can_pause(&escrow.depositor));
+    }
+    #[test]
+    fn test_oracle_schedule_uses_oracle_amount() {
+        let mut escrow = legacy_escrow().upgrade();
+        escrow.release_schedule = ReleaseSchedule::Custom { data: vec![] };
+        assert!(escrow.calculate_available_with(150, Some(400)).is_err());
+        escrow.release_schedule = ReleaseSchedule::Oracle { oracle_program: Pubkey::new_unique(), data: vec![] };
+        assert!(escrow.calculate_available(150).is_err());
+        assert_eq!(escrow.calculate_available_with(150, Some(400)).unwrap(), 150);
+        assert_eq!(escrow.calculate_available_with(150, Some(u64::MAX)).unwrap(), 750);
+    }
+    #[test]
+    fn test_custom_schedule_keeps_legacy_layout() {
+        let bytes = [4, 3, 0, 0, 0, 1, 2, 3];
+        for schedule in [
+            ReleaseSchedule::try_from_slice(&bytes).unwrap(),
+            ReleaseScheduleV3::try_from_slice(&bytes).unwrap().upgrade(),
+        ] {
+            assert!(matches!(schedule, ReleaseSchedule::Custom { data } if data == [1, 2, 3]));
+        }
+    }
+    #[test]
+    fn test_steps_release_cumulative_amounts() {
+        let steps = |pairs: &[(i64, u64)]| pairs
+            .iter()
+            .map(|&(timestamp, cumulative_amount)| StepEntry { timestamp, cumulative_amount })
+            .collect::<Vec<_>>();
+        let entries = steps(&[(100, 250), (130, 500), (160, 750), (190, 1_000)]);
+        assert!(ReleaseSchedule::validate_steps(&entries, 1_000, 90).is_ok());
+        assert!(ReleaseSchedule::validate_steps(&entries, 1_000, 89).is_err());
+        assert!(ReleaseSchedule::validate_steps(&entries, 999, 90).is_err());
+        assert!(ReleaseSchedule::validate_steps(&steps(&[(100, 500), (100, 1_000)]), 1_000, 90).is_err());
+        assert!(ReleaseSchedule::validate_steps(&steps(&[(100, 500), (130, 500), (160, 1_000)]), 1_000, 90).is_err());
+        assert!(ReleaseSchedule::validate_steps(&[], 0, 90).is_err());
+        let full = ReleaseSchedule::Steps { entries: vec![entries[0]; MAX_STEP_ENTRIES] };
+        assert!(full.try_to_vec().unwrap().len() <= 256);
+        assert!(ReleaseSchedule::validate_steps(&steps(&[(i64::MIN, 500), (i64::MAX, 1_000)]), 1_000, i64::MAX).is_err());
+        let mut escrow = legacy_escrow().upgrade();
+        escrow.released_amount = 0;
+        escrow.release_schedule = ReleaseSchedule::Steps { entries };
+        assert_eq!(escrow.calculate_available(99).unwrap(), 0);
+        assert_eq!(escrow.calculate_available(100).unwrap(), 250);
+        assert_eq!(escrow.calculate_available(159).unwrap(), 500);
+        assert_eq!(escrow.calculate_available(500).unwrap(), 1_000);
+    }
+    #[test]
+    fn test_missed_milestones_follow_default_outcome() {
+        let milestone = |milestone_id, missed_outcome| MilestoneCondition {
+            milestone_id,
+            amount: 500,
+            required_approval: Pubkey::new_unique(),
+            is_completed: false,
+            deadline: Some(150),
+            missed_outcome,
+            is_refunded: false,
+            is_submitted: false,
+        };
+        let mut escrow = legacy_escrow().upgrade();
+        escrow.released_amount = 0;
+        escrow.release_schedule = ReleaseSchedule::Milestone {
+            conditions: vec![
+                milestone(0, MissedMilestoneOutcome::PayBeneficiary),
+                milestone(1, MissedMilestoneOutcome::RefundDepositor),
+            ],
+        };
+        assert_eq!(escrow.calculate_available(150).unwrap(), 0);
+        assert!(!escrow.has_missed_milestones_owed(150));
+        assert_eq!(escrow.calculate_available(151).unwrap(), 500);
+        assert!(escrow.has_missed_milestones_owed(151));
+        let conditions = escrow.release_schedule.milestones();
+        assert!(!conditions[0].is_refundable(151) && conditions[1].is_refundable(151));
+        assert!(ReleaseSchedule::validate_milestone_deadlines(conditions, 149).is_ok());
+        assert!(ReleaseSchedule::validate_milestone_deadlines(conditions, 150).is_err());
+        escrow.pause(140).unwrap();
+        escrow.resume(200, true).unwrap();
+        assert_eq!(escrow.calculate_available(210).unwrap(), 0);
+        assert_eq!(escrow.calculate_available(211).unwrap(), 500);
+    }
+    #[test]
+    fn test_submitted_milestones_complete_once() {
+        let conditions = vec![MilestoneCondition {
+            milestone_id: 7,
+            amount: 1_000,
+            required_approval: Pubkey::new_unique(),
+            is_completed: false,
+            deadline: Some(300),
+            missed_outcome: MissedMilestoneOutcome::RefundDepositor,
+            is_refunded: false,
+            is_submitted: false,
+        }];
+        let mut schedule = ReleaseSchedule::Milestone { conditions };
+        assert!(schedule.open_milestone(8, 100).is_err());
+        assert!(schedule.complete_milestone(7, 301).is_err());
+        schedule.complete_milestone(7, 300).unwrap();
+        assert!(schedule.milestones()[0].is_completed);
+        assert!(schedule.complete_milestone(7, 300).is_err());
+        assert!(ReleaseSchedule::Linear { start: 0, end: 1 }.open_milestone(7, 100).is_err());
+        let submission = MilestoneSubmission {
+            escrow_vault: Pubkey::new_unique(),
+            milestone_id: 7,
+            submitter: Pubkey::new_unique(),
+            deliverable_hash: [1; 32],
+            submitted_at: 100,
+            review_deadline: 200,
+            bump: 255,
+        };
+        assert!(!submission.review_elapsed(200));
+        assert!(submission.review_elapsed(201));
+    }
+    #[test]
+    fn test_milestone_caps_fit_schedule_space() {
+        let conditions = |count: usize| (0..count as u32).map(|milestone_id| MilestoneCondition {
+            milestone_id,
+            amount: 1,
+            required_approval: Pubkey::new_unique(),
+            is_completed: false,
+            deadline: Some(i64::MAX),
+            missed_outcome: MissedMilestoneOutcome::PayBeneficiary,
+            is_refunded: false,
+            is_submitted: false,
+        }).collect::<Vec<_>>();
+        let hybrid = |count| ReleaseSchedule::Hybrid {
+            linear_portion: 1,
+            milestone_portion: count as u64,
+            linear_config: LinearConfig { start_time: 0, end_time: 1, acceleration_type: AccelerationType::Linear },
+            milestone_config: conditions(count),
+        };
+        let size = |schedule: ReleaseSchedule| schedule.try_to_vec().unwrap().len();
+        assert!(size(ReleaseSchedule::Milestone { conditions: conditions(MAX_MILESTONES) }) <= 256);
+        assert!(size(ReleaseSchedule::Milestone { conditions: conditions(MAX_MILESTONES + 1) }) > 256);
+        assert!(size(hybrid(MAX_HYBRID_MILESTONES)) <= 256);
+        assert!(size(hybrid(MAX_HYBRID_MILESTONES + 1)) > 256);
+    }
+    #[test]
+    fn test_inclusive_fee_needs_proportional_schedule() {
+        let mut escrow = legacy_escrow().upgrade();
+        assert!(escrow.supports_inclusive_fee());
+        escrow.release_schedule = ReleaseSchedule::Steps {
+            entries: vec![StepEntry { timestamp: 100, cumulative_amount: 1_000 }],
+        };
+        assert!(!escrow.supports_inclusive_fee());
+        escrow.release_schedule = ReleaseSchedule::Milestone { conditions: vec![] };
+        assert!(!escrow.supports_inclusive_fee());
+        escrow.release_schedule = ReleaseSchedule::Linear { start: 100, end: 200 };
+        escrow.release_authority = ReleaseAuthority::Program(Pubkey::new_unique());
+        assert!(!escrow.supports_inclusive_fee());
+    }
+    #[test]
+    fn test_deposit_fee_modes_split_amount() {
+        let mut config = test_config();
+        config.fee_basis_points = 100;
+        assert_eq!(config.split_deposit(1_000_000).unwrap(), (1_000_000, 10_000));
+        config.fee_mode = FeeMode::Inclusive;
+        assert_eq!(config.split_deposit(1_000_000).unwrap(), (990_000, 10_000));
+        assert_eq!(config.split_deposit(99).unwrap(), (99, 0));
+    }
+    #[test]
+    fn test_settle_pays_vested_and_refunds_rest() {
+        let mut escrow = legacy_escrow().upgrade();
+        assert_eq!(escrow.settle(250, 750).unwrap(), (250, 500));
+        assert_eq!((escrow.released_amount, escrow.refunded_amount), (500, 500));
+        assert!(escrow.status == EscrowStatus::Cancelled && escrow.is_settled());
+        let mut escrow = legacy_escrow().upgrade();
+        assert_eq!(escrow.settle(u64::MAX, 750).unwrap(), (750, 0));
+        assert_eq!(escrow.settle(0, 0).unwrap(), (0, 0));
+    }
+    #[test]
+    fn test_expired_escrow_vests_up_to_expiry() {
+        let mut escrow = legacy_escrow().upgrade();
+        escrow.released_amount = 0;
+        escrow.expires_at = Some(150);
+        assert_eq!(escrow.calculate_available(150).unwrap(), 500);
+        assert!(escrow.calculate_available(151).is_err());
+    }
+    #[test]
+    fn test_missed_milestones_refund_once() {
+        let milestone = |milestone_id, deadline, missed_outcome| MilestoneCondition {
+            milestone_id,
+            amount: 250,
+            required_approval: Pubkey::new_unique(),
+            is_completed: false,
+            deadline: Some(deadline),
+            missed_outcome,
+            is_refunded: false,
+            is_submitted: false,
+        };
+        let mut escrow = legacy_escrow().upgrade();
+        escrow.release_schedule = ReleaseSchedule::Milestone {
+            conditions: vec![
+                milestone(0, 150, MissedMilestoneOutcome::RefundDepositor),
+                milestone(1, 150, MissedMilestoneOutcome::PayBeneficiary),
+                milestone(2, 150, MissedMilestoneOutcome::RefundDepositor),
+                milestone(3, 200, MissedMilestoneOutcome::RefundDepositor),
+            ],
+        };
+        assert!(escrow.refund_missed_milestones(150).is_err());
+        assert_eq!(escrow.refund_missed_milestones(151).unwrap(), (vec![0, 2], 500));
+        assert!(escrow.refund_missed_milestones(151).is_err());
+        assert_eq!(escrow.refund_missed_milestones(201).unwrap(), (vec![3], 250));
+        escrow.release_schedule = ReleaseSchedule::Immediate;
+        assert!(escrow.refund_missed_milestones(201).is_err());
+    }
+    #[test]
+    fn test_submission_leaves_review_window_before_deadline() {
+        let mut escrow = legacy_escrow().upgrade();
+        escrow.release_schedule = ReleaseSchedule::Milestone {
+            conditions: vec![MilestoneCondition {
+                milestone_id: 0,
+                amount: 1_000,
+                required_approval: Pubkey::new_unique(),
+                is_completed: false,
+                deadline: Some(200),
+                missed_outcome: MissedMilestoneOutcome::RefundDepositor,
+                is_refunded: false,
+                is_submitted: false,
+            }],
+        };
+        assert!(escrow.clone().submit_milestone(0, 150, 50).is_err());
+        assert!(escrow.clone().submit_milestone(1, 100, 50).is_err());
+        escrow.total_paused_duration = 10;
+        assert_eq!(escrow.submit_milestone(0, 159, 50).unwrap(), (149, 199));
+        escrow.paused_at = Some(170);
+        let submission = MilestoneSubmission {
+            escrow_vault: Pubkey::new_unique(),
+            milestone_id: 0,
+            submitter: Pubkey::new_unique(),
+            deliverable_hash: [1; 32],
+            submitted_at: 149,
+            review_deadline: 199,
+            bump: 255,
+        };
+        assert!(!submission.review_elapsed(escrow.schedule_time(1_000)));
+        escrow.paused_at = None;
+        assert!(submission.review_elapsed(escrow.schedule_time(1_000)));
+        assert!(escrow.refund_missed_milestones(1_000).is_err());
+        escrow.release_schedule.complete_milestone(0, submission.submitted_at).unwrap();
+        assert_eq!(escrow.calculate_available(1_000).unwrap(), 750);
+    }
 }
\ No newline at end of file